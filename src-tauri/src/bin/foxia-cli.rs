@@ -0,0 +1,196 @@
+use clap::{Parser, Subcommand};
+use foxia_mkt_lib::daemon::autostart;
+use foxia_mkt_lib::odoo::client::OdooClient;
+use foxia_mkt_lib::s3_transfer;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Headless companion to the Foxia GUI: login, profile sync, and autostart
+/// management without a running Tauri app. Intended for CI boxes / servers
+/// and for scheduling sync jobs outside the desktop app.
+#[derive(Parser)]
+#[command(name = "foxia-cli")]
+struct Cli {
+  /// Odoo session token, overriding the token file / FOXIA_SESSION_TOKEN env var.
+  #[arg(long, global = true)]
+  token: Option<String>,
+
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Log in to Odoo and persist the session token for subsequent commands.
+  Login {
+    #[arg(long)]
+    base_url: String,
+    #[arg(long)]
+    login: String,
+    #[arg(long)]
+    password: String,
+  },
+  /// Zip and upload a profile's data dir to Odoo S3.
+  Push {
+    profile_id: String,
+    #[arg(long)]
+    base_url: String,
+  },
+  /// Download and extract a profile bundle from a given URL.
+  Pull { profile_id: String, url: String },
+  /// List profiles registered in Odoo.
+  Ls {
+    #[arg(long, default_value_t = 0)]
+    offset: u32,
+    #[arg(long, default_value_t = 50)]
+    limit: u32,
+    #[arg(long)]
+    base_url: String,
+  },
+  /// Manage autostart of the background daemon.
+  Autostart {
+    #[command(subcommand)]
+    action: AutostartAction,
+  },
+}
+
+#[derive(Subcommand)]
+enum AutostartAction {
+  Enable,
+  Disable,
+  Status,
+}
+
+fn token_path() -> PathBuf {
+  autostart::get_data_dir()
+    .unwrap_or_else(|| PathBuf::from("."))
+    .join("session_token")
+}
+
+fn save_token(session_id: &str) -> std::io::Result<()> {
+  let path = token_path();
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, session_id)
+}
+
+/// Resolve the session token in priority order: `--token` flag,
+/// `FOXIA_SESSION_TOKEN` env var, then the token file written by `login`.
+fn resolve_token(cli_token: &Option<String>) -> Result<String, String> {
+  if let Some(token) = cli_token {
+    return Ok(token.clone());
+  }
+  if let Ok(token) = std::env::var("FOXIA_SESSION_TOKEN") {
+    return Ok(token);
+  }
+  std::fs::read_to_string(token_path())
+    .map(|s| s.trim().to_string())
+    .map_err(|_| "Not logged in: no session token found (run `foxia-cli login` first, or pass --token / FOXIA_SESSION_TOKEN)".to_string())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  env_logger::init();
+  let cli = Cli::parse();
+
+  match cli.command {
+    Command::Login {
+      base_url,
+      login,
+      password,
+    } => {
+      let client = OdooClient::new(base_url);
+      let result = client
+        .login(login, secrecy::SecretString::new(password))
+        .await?;
+      let session_id = result
+        .session_id
+        .ok_or("Login succeeded but no session_id was returned")?;
+      save_token(&session_id)?;
+      eprintln!("Logged in as {}, session token saved", result.name);
+    }
+    Command::Push { profile_id, base_url } => {
+      let token = resolve_token(&cli.token)?;
+      eprintln!("Zipping and uploading profile {}...", profile_id);
+
+      let profiles_dir = foxia_mkt_lib::profile::manager::ProfileManager::instance().get_profiles_dir();
+      let profiles = foxia_mkt_lib::profile::manager::ProfileManager::instance()
+        .list_profiles()
+        .map_err(|e| e.to_string())?;
+      let profile_uuid = uuid::Uuid::parse_str(&profile_id)?;
+      let profile = profiles
+        .iter()
+        .find(|p| p.id == profile_uuid)
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+      let profile_data_dir = profile.get_profile_data_path(&profiles_dir);
+
+      let temp_dir = std::env::temp_dir().join("foxia-profile-sync");
+      std::fs::create_dir_all(&temp_dir)?;
+      let zip_path = temp_dir.join(format!("{}.zip", profile.name));
+      s3_transfer::zip_directory(&profile_data_dir, &zip_path)?;
+
+      let profile_url = s3_transfer::upload_profile_to_s3(&base_url, &token, &zip_path).await?;
+      let _ = std::fs::remove_file(&zip_path);
+
+      eprintln!("Uploaded. Profile URL: {}", profile_url);
+      println!("{}", profile_url);
+    }
+    Command::Pull { profile_id, url } => {
+      let profiles_dir = foxia_mkt_lib::profile::manager::ProfileManager::instance().get_profiles_dir();
+      let profiles = foxia_mkt_lib::profile::manager::ProfileManager::instance()
+        .list_profiles()
+        .map_err(|e| e.to_string())?;
+      let profile_uuid = uuid::Uuid::parse_str(&profile_id)?;
+      let profile = profiles
+        .iter()
+        .find(|p| p.id == profile_uuid)
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+      let profile_data_dir = profile.get_profile_data_path(&profiles_dir);
+
+      s3_transfer::download_and_extract_profile_with_progress(&url, &profile_data_dir, |downloaded, total| {
+        eprint!("\rDownloading... {}/{} bytes", downloaded, total);
+        let _ = std::io::stderr().flush();
+      })
+      .await?;
+      eprintln!("\nDownload complete.");
+    }
+    Command::Ls {
+      offset,
+      limit,
+      base_url,
+    } => {
+      let token = resolve_token(&cli.token)?;
+      // The CLI authenticates with a pre-existing session token rather than
+      // logging in again, so seed the client's auth with it directly.
+      let client = OdooClient::new(base_url).with_session_token(token);
+      let result = client.list_profiles(offset, limit).await?;
+      for profile in &result.items {
+        println!("{}\t{}", profile.id, profile.name);
+      }
+      eprintln!("{} of {} total", result.items.len(), result.total_count);
+    }
+    Command::Autostart { action } => match action {
+      AutostartAction::Enable => {
+        autostart::enable_autostart()?;
+        eprintln!("Autostart enabled.");
+      }
+      AutostartAction::Disable => {
+        autostart::disable_autostart()?;
+        eprintln!("Autostart disabled.");
+      }
+      AutostartAction::Status => {
+        println!(
+          "{}",
+          if autostart::is_autostart_enabled() {
+            "enabled"
+          } else {
+            "disabled"
+          }
+        );
+      }
+    },
+  }
+
+  Ok(())
+}
@@ -0,0 +1,164 @@
+use crate::browser::ProxySettings;
+use crate::profile::prefs::{PrefStore, PrefValue};
+use crate::profile::types::BrowserProfile;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A single typed Firefox preference value a user wants materialized into
+/// a Camoufox profile's `user.js`/`prefs.js`, mirroring the three literal
+/// shapes `about:config` itself supports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum CamoufoxPrefValue {
+  Bool(bool),
+  Int(i64),
+  Str(String),
+}
+
+impl From<&CamoufoxPrefValue> for PrefValue {
+  fn from(value: &CamoufoxPrefValue) -> Self {
+    match value {
+      CamoufoxPrefValue::Bool(b) => PrefValue::Bool(*b),
+      CamoufoxPrefValue::Int(i) => PrefValue::Int(*i),
+      CamoufoxPrefValue::Str(s) => PrefValue::Str(s.clone()),
+    }
+  }
+}
+
+/// WebDriver-style fingerprint capabilities: the handful of
+/// commonly-spoofed signals exposed as named knobs instead of requiring a
+/// user to know the underlying pref/fingerprint-JSON key, the same way a
+/// WebDriver `capabilities` object wraps concrete browser settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FingerprintCapabilities {
+  #[serde(default)]
+  pub screen_width: Option<u32>,
+  #[serde(default)]
+  pub screen_height: Option<u32>,
+  #[serde(default)]
+  pub timezone: Option<String>,
+  #[serde(default)]
+  pub locale: Option<String>,
+  #[serde(default)]
+  pub webgl_vendor: Option<String>,
+  #[serde(default)]
+  pub navigator_platform: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CamoufoxConfig {
+  pub executable_path: Option<String>,
+  /// Camoufox's own generated fingerprint-injection config, a JSON object
+  /// of dotted keys (`navigator.userAgent`, `headers.User-Agent`, ...).
+  pub fingerprint: Option<String>,
+  pub os: Option<String>,
+  pub proxy: Option<ProxySettings>,
+  /// Arbitrary user-defined `about:config` prefs, applied on top of
+  /// whatever the generated fingerprint already set. Validated by
+  /// `CamoufoxManager::validate_custom_prefs` when a config is saved via
+  /// `update_camoufox_config`, so a malformed key is rejected then
+  /// instead of being silently dropped at launch.
+  #[serde(default)]
+  pub custom_prefs: HashMap<String, CamoufoxPrefValue>,
+  /// Higher-level fingerprint knobs, so the user doesn't hand-write the
+  /// underlying pref names. At launch `apply_custom_prefs` writes only
+  /// `locale` and `navigator_platform` as prefs; the other knobs reach the
+  /// browser only through the fingerprint JSON that
+  /// `generate_fingerprint_config` builds, which is not regenerated at
+  /// launch.
+  #[serde(default)]
+  pub capabilities: Option<FingerprintCapabilities>,
+}
+
+pub struct CamoufoxManager;
+
+impl CamoufoxManager {
+  pub fn instance() -> &'static CamoufoxManager {
+    &CAMOUFOX_MANAGER
+  }
+
+  /// Build the fingerprint-injection JSON for `profile`, folding in any
+  /// `capabilities` on top of whatever the config already carries.
+  pub async fn generate_fingerprint_config(
+    &self,
+    _app_handle: &tauri::AppHandle,
+    _profile: &BrowserProfile,
+    config: &CamoufoxConfig,
+  ) -> Result<String, Box<dyn std::error::Error>> {
+    let mut fp: serde_json::Map<String, serde_json::Value> = match &config.fingerprint {
+      Some(existing) => serde_json::from_str(existing).unwrap_or_default(),
+      None => serde_json::Map::new(),
+    };
+
+    if let Some(caps) = &config.capabilities {
+      if let (Some(width), Some(height)) = (caps.screen_width, caps.screen_height) {
+        fp.insert("screen.width".to_string(), json!(width));
+        fp.insert("screen.height".to_string(), json!(height));
+      }
+      if let Some(timezone) = &caps.timezone {
+        fp.insert("timezone".to_string(), json!(timezone));
+      }
+      if let Some(locale) = &caps.locale {
+        fp.insert("navigator.language".to_string(), json!(locale));
+      }
+      if let Some(webgl_vendor) = &caps.webgl_vendor {
+        fp.insert("webGl:vendor".to_string(), json!(webgl_vendor));
+      }
+      if let Some(platform) = &caps.navigator_platform {
+        fp.insert("navigator.platform".to_string(), json!(platform));
+      }
+    }
+
+    Ok(serde_json::to_string(&fp)?)
+  }
+
+  /// Look up a running Camoufox process bound to the given profile data
+  /// path. No process lookup is performed: this always returns `Ok(None)`.
+  pub async fn find_camoufox_by_profile(&self, _profile_data_path: &str) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+    Ok(None)
+  }
+
+  /// Reject a malformed pref key (empty, containing characters
+  /// `about:config` would never produce, or with an empty dotted
+  /// segment) at save time, rather than writing it to `user.js` and
+  /// having Firefox silently ignore it at launch.
+  pub fn validate_custom_prefs(&self, prefs: &HashMap<String, CamoufoxPrefValue>) -> Result<(), String> {
+    for key in prefs.keys() {
+      if key.is_empty() || key.starts_with('.') || key.ends_with('.') || key.contains("..") {
+        return Err(format!("Invalid pref key '{}': malformed about:config-style name", key));
+      }
+      let valid_chars = key.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_');
+      if !valid_chars {
+        return Err(format!("Invalid pref key '{}': contains characters about:config prefs can't have", key));
+      }
+    }
+    Ok(())
+  }
+
+  /// Write `custom_prefs` (and any capability-derived prefs) into the
+  /// profile's `user.js`, merging into whatever's already there rather
+  /// than clobbering it.
+  pub fn apply_custom_prefs(&self, profile_data_dir: &Path, config: &CamoufoxConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let user_js = profile_data_dir.join("user.js");
+    PrefStore::update(&user_js, |prefs| {
+      for (key, value) in &config.custom_prefs {
+        prefs.set(key.clone(), PrefValue::from(value));
+      }
+      if let Some(caps) = &config.capabilities {
+        if let Some(locale) = &caps.locale {
+          prefs.set("intl.accept_languages", locale.clone());
+        }
+        if let Some(platform) = &caps.navigator_platform {
+          prefs.set("general.useragent.override.platform", platform.clone());
+        }
+      }
+    })?;
+    Ok(())
+  }
+}
+
+lazy_static::lazy_static! {
+  static ref CAMOUFOX_MANAGER: CamoufoxManager = CamoufoxManager;
+}
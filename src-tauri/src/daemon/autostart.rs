@@ -147,9 +147,34 @@ pub fn disable_autostart() -> io::Result<()> {
   Ok(())
 }
 
+/// `gui/<uid>` domain target, scoped to the current user's GUI session.
+#[cfg(target_os = "macos")]
+fn gui_domain() -> String {
+  let uid = unsafe { libc::getuid() };
+  format!("gui/{}", uid)
+}
+
+/// `gui/<uid>/com.foxia-mkt.daemon` service target within that domain.
+#[cfg(target_os = "macos")]
+fn service_target() -> String {
+  format!("{}/com.foxia-mkt.daemon", gui_domain())
+}
+
 #[cfg(target_os = "macos")]
 pub fn is_autostart_enabled() -> bool {
-  get_plist_path().is_some_and(|p| p.exists())
+  use std::process::Command;
+
+  if !get_plist_path().is_some_and(|p| p.exists()) {
+    return false;
+  }
+
+  // Verify actual registration in launchd rather than just trusting the
+  // plist file is present on disk.
+  Command::new("launchctl")
+    .args(["print", &service_target()])
+    .output()
+    .map(|output| output.status.success())
+    .unwrap_or(false)
 }
 
 #[cfg(target_os = "macos")]
@@ -166,25 +191,25 @@ pub fn load_launch_agent() -> io::Result<()> {
     ));
   }
 
-  // Use launchctl load to start the daemon via launchd
-  // The -w flag writes the "disabled" key to the override plist
+  // Domain-aware bootstrap replaces the deprecated `launchctl load -w`,
+  // which silently no-ops outside the right session context.
   let output = Command::new("launchctl")
-    .args(["load", "-w"])
+    .args(["bootstrap", &gui_domain()])
     .arg(&plist_path)
     .output()?;
 
   if !output.status.success() {
     let stderr = String::from_utf8_lossy(&output.stderr);
-    // "already loaded" is not an error condition for us
-    if !stderr.contains("already loaded") {
+    // Already-bootstrapped is not an error condition for us
+    if !stderr.contains("already bootstrapped") && !stderr.contains("Service already loaded") {
       return Err(io::Error::other(format!(
-        "launchctl load failed: {}",
+        "launchctl bootstrap failed: {}",
         stderr
       )));
     }
   }
 
-  log::info!("Loaded launch agent via launchctl");
+  log::info!("Bootstrapped launch agent via launchctl in {}", gui_domain());
   Ok(())
 }
 
@@ -200,19 +225,41 @@ pub fn unload_launch_agent() -> io::Result<()> {
   }
 
   let output = Command::new("launchctl")
-    .args(["unload"])
-    .arg(&plist_path)
+    .args(["bootout", &service_target()])
     .output()?;
 
   if !output.status.success() {
     let stderr = String::from_utf8_lossy(&output.stderr);
-    // Not being loaded is not an error
-    if !stderr.contains("Could not find specified service") {
-      log::warn!("launchctl unload warning: {}", stderr);
+    // Not being bootstrapped is not an error
+    if !stderr.contains("no such process") && !stderr.contains("Could not find specified service")
+    {
+      log::warn!("launchctl bootout warning: {}", stderr);
     }
   }
 
-  log::info!("Unloaded launch agent via launchctl");
+  log::info!("Booted out launch agent via launchctl in {}", gui_domain());
+  Ok(())
+}
+
+/// Force-restart an already-bootstrapped daemon, e.g. right after an
+/// upgrade replaced the binary on disk.
+#[cfg(target_os = "macos")]
+pub fn restart_launch_agent() -> io::Result<()> {
+  use std::process::Command;
+
+  let output = Command::new("launchctl")
+    .args(["kickstart", "-k", &service_target()])
+    .output()?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(io::Error::other(format!(
+      "launchctl kickstart failed: {}",
+      stderr
+    )));
+  }
+
+  log::info!("Kickstarted launch agent via launchctl in {}", gui_domain());
   Ok(())
 }
 
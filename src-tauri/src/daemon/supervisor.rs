@@ -0,0 +1,131 @@
+use crate::runner::{BrowserRunner, Runner, RunnerProcess};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Current lifecycle state for a profile's browser process, as observed by
+/// the supervisor and surfaced to the UI over the `profiles-changed` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessState {
+  Running,
+  Stopped,
+  Crashed,
+}
+
+struct ManagedProcess {
+  process: RunnerProcess,
+  binary_path: PathBuf,
+  profile_data_dir: PathBuf,
+  keep_alive: bool,
+  last_state: ProcessState,
+}
+
+lazy_static::lazy_static! {
+  static ref MANAGED_PROCESSES: Mutex<HashMap<String, ManagedProcess>> =
+    Mutex::new(HashMap::new());
+}
+
+/// Launch a profile's browser and start tracking it. If `keep_alive` is
+/// true, the supervisor restarts it whenever it exits with a non-zero
+/// status, mirroring launchd's `KeepAlive/SuccessfulExit=false` semantics.
+pub fn launch_profile_process(
+  profile_id: String,
+  binary_path: PathBuf,
+  profile_data_dir: PathBuf,
+  keep_alive: bool,
+) -> std::io::Result<u32> {
+  let process = BrowserRunner::new(&binary_path, &profile_data_dir).start()?;
+  let pid = process.id();
+
+  let mut processes = MANAGED_PROCESSES.lock().unwrap();
+  processes.insert(
+    profile_id,
+    ManagedProcess {
+      process,
+      binary_path,
+      profile_data_dir,
+      keep_alive,
+      last_state: ProcessState::Running,
+    },
+  );
+  Ok(pid)
+}
+
+pub fn stop_profile_process(profile_id: &str) -> std::io::Result<()> {
+  let mut processes = MANAGED_PROCESSES.lock().unwrap();
+  if let Some(mut managed) = processes.remove(profile_id) {
+    managed.process.kill()?;
+  }
+  Ok(())
+}
+
+pub fn profile_state(profile_id: &str) -> Option<ProcessState> {
+  let processes = MANAGED_PROCESSES.lock().unwrap();
+  processes.get(profile_id).map(|m| m.last_state)
+}
+
+/// Poll every tracked process once, advancing its `last_state` and
+/// restarting it in-place when it crashed and `keep_alive` is set. Returns
+/// the set of profile ids whose state changed, so the caller can emit
+/// `profiles-changed` only when something actually happened.
+pub fn poll_managed_processes() -> Vec<String> {
+  let mut processes = MANAGED_PROCESSES.lock().unwrap();
+  let mut changed = Vec::new();
+  let mut to_restart = Vec::new();
+
+  for (profile_id, managed) in processes.iter_mut() {
+    match managed.process.try_status() {
+      Ok(Some(status)) => {
+        log::warn!("Profile {} process exited with {:?}", profile_id, status);
+        let new_state = if status.success() {
+          ProcessState::Stopped
+        } else {
+          ProcessState::Crashed
+        };
+        if managed.keep_alive && !status.success() {
+          to_restart.push(profile_id.clone());
+        }
+        if managed.last_state != new_state {
+          managed.last_state = new_state;
+          changed.push(profile_id.clone());
+        }
+      }
+      Ok(None) => {}
+      Err(e) => {
+        log::error!("Failed to poll profile {} process: {}", profile_id, e);
+      }
+    }
+  }
+
+  for profile_id in to_restart {
+    if let Some(managed) = processes.get(&profile_id) {
+      let binary_path = managed.binary_path.clone();
+      let profile_data_dir = managed.profile_data_dir.clone();
+      match BrowserRunner::new(&binary_path, &profile_data_dir).start() {
+        Ok(new_process) => {
+          log::info!("Restarting crashed profile {} (keep_alive)", profile_id);
+          if let Some(managed) = processes.get_mut(&profile_id) {
+            managed.process = new_process;
+            managed.last_state = ProcessState::Running;
+          }
+        }
+        Err(e) => log::error!("Failed to restart profile {}: {}", profile_id, e),
+      }
+    }
+  }
+
+  changed
+}
+
+/// Background task that polls tracked processes on an interval and emits
+/// `profiles-changed` whenever one of them transitions state (exited,
+/// crashed, or was auto-restarted).
+pub async fn spawn_supervisor_loop() {
+  loop {
+    let changed = poll_managed_processes();
+    if !changed.is_empty() {
+      let _ = crate::events::emit_empty("profiles-changed");
+    }
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+  }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use std::env;
+
+/// Variables whose bundle-injected values commonly leak into spawned child
+/// processes and make an external (non-bundled) browser load the wrong
+/// libraries or toolkit.
+const PATHLIST_VARS: &[&str] = &[
+  "LD_LIBRARY_PATH",
+  "GST_PLUGIN_SYSTEM_PATH",
+  "GTK_PATH",
+  "GIO_MODULE_DIR",
+  "XDG_DATA_DIRS",
+  "XDG_CONFIG_DIRS",
+  "PATH",
+];
+
+pub fn is_appimage() -> bool {
+  env::var_os("APPDIR").is_some() || env::var_os("APPIMAGE").is_some()
+}
+
+pub fn is_flatpak() -> bool {
+  std::path::Path::new("/.flatpak-info").exists()
+}
+
+pub fn is_snap() -> bool {
+  env::var_os("SNAP").is_some()
+}
+
+/// Returns the prefix under which the current bundle installs its own
+/// copies of shared libraries/data, if we're running inside one. Any
+/// path-list entry rooted under this prefix is assumed to be
+/// bundle-injected rather than something the user configured.
+fn bundle_prefix() -> Option<String> {
+  if is_appimage() {
+    env::var("APPDIR").ok()
+  } else if is_flatpak() {
+    Some("/app".to_string())
+  } else if is_snap() {
+    env::var("SNAP").ok()
+  } else {
+    None
+  }
+}
+
+/// Split a `:`-separated path list, drop entries rooted under the bundle
+/// prefix, drop empty entries, and de-duplicate while keeping the *last*
+/// occurrence of a repeated directory (so a later, lower-priority override
+/// wins the same way it would have in the original list order).
+pub fn normalize_pathlist(var: &str) -> Option<String> {
+  let raw = env::var(var).ok()?;
+  let prefix = bundle_prefix();
+
+  let mut seen = HashSet::new();
+  let mut kept: Vec<&str> = Vec::new();
+
+  for entry in raw.split(':').rev() {
+    if entry.is_empty() {
+      continue;
+    }
+    if let Some(prefix) = &prefix {
+      if entry.starts_with(prefix.as_str()) {
+        continue;
+      }
+    }
+    if seen.insert(entry) {
+      kept.push(entry);
+    }
+  }
+  kept.reverse();
+
+  if kept.is_empty() {
+    None
+  } else {
+    Some(kept.join(":"))
+  }
+}
+
+/// Build a clean environment for spawning an external (non-bundled)
+/// process: for each known path-list variable, restore the launcher's
+/// pre-bundle backup (`<VAR>_ORIG`) if one was stashed, otherwise fall back
+/// to the normalized current value. Variables that end up empty are
+/// removed entirely rather than set to `""`.
+pub fn sanitized_env() -> Vec<(String, String)> {
+  let mut result = Vec::new();
+
+  for &var in PATHLIST_VARS {
+    let orig_var = format!("{}_ORIG", var);
+    let restored = env::var(&orig_var).ok().filter(|v| !v.is_empty());
+
+    let value = match restored {
+      Some(v) => Some(v),
+      None => normalize_pathlist(var),
+    };
+
+    if let Some(value) = value {
+      result.push((var.to_string(), value));
+    }
+  }
+
+  result
+}
+
+/// Apply `sanitized_env()` onto an arbitrary `Runner`/`Command`-like
+/// builder. Only variables with a non-empty sanitized value are set; a
+/// var that normalizes away entirely is left unset rather than forced to
+/// `""`, since an empty `LD_LIBRARY_PATH` etc. is not the same as an
+/// absent one to the dynamic linker.
+pub fn apply_to<R: crate::runner::Runner>(runner: &mut R) -> &mut R {
+  for (key, value) in sanitized_env() {
+    runner.env(key, value);
+  }
+  runner
+}
@@ -0,0 +1,202 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const KEYRING_SERVICE: &str = "foxia-mkt";
+const KEYRING_ACCOUNT: &str = "macaroon-root-key";
+
+#[derive(Debug)]
+pub struct MacaroonError(String);
+
+impl fmt::Display for MacaroonError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "macaroon error: {}", self.0)
+  }
+}
+impl std::error::Error for MacaroonError {}
+
+/// Look up (or lazily create) the 32-byte HMAC root key in the OS keyring,
+/// mirroring `crate::vault::vault_key` but under its own keyring account so
+/// leaking one secret doesn't compromise the other. Falls back to deriving
+/// a key from `FOXIA_MACAROON_ROOT_KEY` when no keyring is available; on a
+/// headless box with that unset there is no source-visible key to fall
+/// back to, so this fails loudly instead of deriving a publicly-known
+/// constant every install would share.
+fn root_key() -> Result<[u8; 32], MacaroonError> {
+  if let Ok(entry) = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT) {
+    if let Ok(existing) = entry.get_password() {
+      if let Ok(bytes) = STANDARD.decode(existing) {
+        if bytes.len() == 32 {
+          let mut key = [0u8; 32];
+          key.copy_from_slice(&bytes);
+          return Ok(key);
+        }
+      }
+    }
+
+    let mut key = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    let _ = entry.set_password(&STANDARD.encode(key));
+    return Ok(key);
+  }
+
+  const SALT: &[u8] = b"foxia-mkt-macaroon-salt-v1";
+  let passphrase = std::env::var("FOXIA_MACAROON_ROOT_KEY").map_err(|_| {
+    MacaroonError(
+      "no OS keyring available and FOXIA_MACAROON_ROOT_KEY is unset; refusing to derive a root key from a hardcoded passphrase".to_string(),
+    )
+  })?;
+  let mut key = [0u8; 32];
+  argon2::Argon2::default()
+    .hash_password_into(passphrase.as_bytes(), SALT, &mut key)
+    .expect("Argon2id key derivation must not fail for a fixed-size output");
+  Ok(key)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Caveat {
+  key: String,
+  value: String,
+}
+
+impl Caveat {
+  fn to_bytes(&self) -> Vec<u8> {
+    format!("{}={}", self.key, self.value).into_bytes()
+  }
+}
+
+/// An HMAC caveat chain: `signature` starts as `HMAC(root_key, identifier)`
+/// and each caveat folds the previous signature in as the next HMAC key,
+/// i.e. `sig = HMAC(prev_sig, caveat_bytes)`. Verifying replays this chain
+/// from the root key, so tampering with the identifier or any caveat (or
+/// reordering them) invalidates every signature after it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Macaroon {
+  identifier: String,
+  caveats: Vec<Caveat>,
+  signature: String,
+}
+
+impl Macaroon {
+  fn mint(identifier: impl Into<String>) -> Result<Self, MacaroonError> {
+    let identifier = identifier.into();
+    let mut mac = HmacSha256::new_from_slice(&root_key()?).expect("HMAC accepts a key of any length");
+    mac.update(identifier.as_bytes());
+    let signature = STANDARD.encode(mac.finalize().into_bytes());
+    Ok(Self {
+      identifier,
+      caveats: Vec::new(),
+      signature,
+    })
+  }
+
+  fn add_caveat(&mut self, key: impl Into<String>, value: impl Into<String>) -> Result<(), MacaroonError> {
+    let caveat = Caveat {
+      key: key.into(),
+      value: value.into(),
+    };
+    let prev_sig = STANDARD
+      .decode(&self.signature)
+      .map_err(|e| MacaroonError(e.to_string()))?;
+    let mut mac = HmacSha256::new_from_slice(&prev_sig).expect("HMAC accepts a key of any length");
+    mac.update(&caveat.to_bytes());
+    self.signature = STANDARD.encode(mac.finalize().into_bytes());
+    self.caveats.push(caveat);
+    Ok(())
+  }
+
+  fn caveat(&self, key: &str) -> Option<&str> {
+    self.caveats.iter().find(|c| c.key == key).map(|c| c.value.as_str())
+  }
+
+  /// Replay the HMAC chain from the root key and compare against the
+  /// stored signature.
+  fn verify_signature(&self) -> Result<(), MacaroonError> {
+    let mut mac = HmacSha256::new_from_slice(&root_key()?).expect("HMAC accepts a key of any length");
+    mac.update(self.identifier.as_bytes());
+    let mut sig = mac.finalize().into_bytes().to_vec();
+
+    for caveat in &self.caveats {
+      let mut mac = HmacSha256::new_from_slice(&sig).expect("HMAC accepts a key of any length");
+      mac.update(&caveat.to_bytes());
+      sig = mac.finalize().into_bytes().to_vec();
+    }
+
+    if STANDARD.encode(&sig) == self.signature {
+      Ok(())
+    } else {
+      Err(MacaroonError("signature chain does not match; macaroon was tampered with".to_string()))
+    }
+  }
+
+  fn serialize(&self) -> Result<String, MacaroonError> {
+    let json = serde_json::to_vec(self).map_err(|e| MacaroonError(e.to_string()))?;
+    Ok(STANDARD.encode(json))
+  }
+
+  fn deserialize(token: &str) -> Result<Self, MacaroonError> {
+    let bytes = STANDARD.decode(token).map_err(|e| MacaroonError(e.to_string()))?;
+    serde_json::from_slice(&bytes).map_err(|e| MacaroonError(e.to_string()))
+  }
+}
+
+/// Mint a serialized, offline-verifiable macaroon granting sync rights for
+/// exactly one profile, expiring `ttl_secs` after `now` (both epoch
+/// seconds). Lets a coordinator hand out per-profile, time-boxed,
+/// least-privilege sync credentials without sharing the master Odoo
+/// password.
+pub fn issue_sync_macaroon(profile_id: &uuid::Uuid, now: u64, ttl_secs: u64) -> Result<String, MacaroonError> {
+  let mut macaroon = Macaroon::mint(format!("sync-{}", profile_id))?;
+  macaroon.add_caveat("profile_id", profile_id.to_string())?;
+  macaroon.add_caveat("action", "sync")?;
+  macaroon.add_caveat("expires", (now + ttl_secs).to_string())?;
+  macaroon.serialize()
+}
+
+/// Verify `token` is a valid, unexpired sync credential scoped to
+/// `profile_id`: the HMAC chain must replay correctly under the root key,
+/// and every embedded caveat predicate (profile scope, action, expiry)
+/// must hold against `now`. Intended to be checked client-side before a
+/// sync request is ever issued over the network.
+pub fn verify_sync_macaroon(token: &str, profile_id: &uuid::Uuid, now: u64) -> Result<(), MacaroonError> {
+  let macaroon = Macaroon::deserialize(token)?;
+  macaroon.verify_signature()?;
+
+  let expected_profile_id = profile_id.to_string();
+  if macaroon.caveat("profile_id") != Some(expected_profile_id.as_str()) {
+    return Err(MacaroonError("macaroon is not scoped to this profile".to_string()));
+  }
+  if macaroon.caveat("action") != Some("sync") {
+    return Err(MacaroonError("macaroon does not grant sync rights".to_string()));
+  }
+
+  let expires: u64 = macaroon
+    .caveat("expires")
+    .and_then(|v| v.parse().ok())
+    .ok_or_else(|| MacaroonError("macaroon is missing an expiry caveat".to_string()))?;
+  if now >= expires {
+    return Err(MacaroonError("macaroon has expired".to_string()));
+  }
+
+  // A holder can append further caveats (repeating a key), so every one
+  // of them is checked, not only the first match `caveat()` returns.
+  for caveat in &macaroon.caveats {
+    let holds = match caveat.key.as_str() {
+      "profile_id" => caveat.value == expected_profile_id,
+      "action" => caveat.value == "sync",
+      "expires" => caveat.value.parse::<u64>().is_ok_and(|exp| now < exp),
+      _ => false,
+    };
+    if !holds {
+      return Err(MacaroonError(format!("macaroon caveat {}={} does not hold", caveat.key, caveat.value)));
+    }
+  }
+
+  Ok(())
+}
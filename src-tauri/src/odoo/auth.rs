@@ -0,0 +1,66 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// How an `OdooClient` authenticates its requests: the classic Odoo
+/// cookie session, or a bearer token obtained from an external OAuth2/
+/// OIDC provider fronting the Odoo instance directly instead of Odoo's
+/// own username/password login.
+#[derive(Debug, Clone)]
+pub enum OdooAuth {
+  Session(String),
+  Bearer {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+  },
+}
+
+impl OdooAuth {
+  /// `true` once the credential is known to no longer be valid. A
+  /// session has no expiry expressed here — Odoo just rejects it when it
+  /// goes stale, handled separately by `OdooClient::try_recover_from_error`
+  /// — so only a `Bearer` token with a known `expires_at` can answer
+  /// `true`.
+  pub fn is_expired(&self) -> bool {
+    match self {
+      OdooAuth::Session(_) => false,
+      OdooAuth::Bearer { expires_at, .. } => expires_at.is_some_and(|exp| exp <= Utc::now()),
+    }
+  }
+
+  /// Attaches this credential to an outgoing request: a `Bearer` token is
+  /// sent as an `Authorization` header, while `Session` is a no-op — the
+  /// cookie jar already carries `session_id` from `login`.
+  pub fn apply(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match self {
+      OdooAuth::Session(_) => builder,
+      OdooAuth::Bearer { access_token, .. } => builder.bearer_auth(access_token),
+    }
+  }
+}
+
+/// Claims decoded from an OIDC provider's ID token, mirroring the
+/// `Userinfo` shape used by Stalwart/inth-oauth2 — just enough to know
+/// who logged in without a second round-trip to the provider's userinfo
+/// endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcUserinfo {
+  pub sub: String,
+  pub name: Option<String>,
+  pub email: Option<String>,
+  #[serde(alias = "preferred_username")]
+  pub login: Option<String>,
+}
+
+/// Decodes (without signature verification — the token was already
+/// validated by the provider over TLS at exchange time, and this is only
+/// used to populate a display name) the claims payload of a JWT ID token.
+pub fn decode_id_token_claims(id_token: &str) -> Result<OidcUserinfo, String> {
+  let payload = id_token.split('.').nth(1).ok_or("ID token is not a well-formed JWT")?;
+  let bytes = URL_SAFE_NO_PAD
+    .decode(payload)
+    .map_err(|e| format!("Failed to base64-decode ID token claims: {}", e))?;
+  serde_json::from_slice(&bytes).map_err(|e| format!("Failed to parse ID token claims: {}", e))
+}
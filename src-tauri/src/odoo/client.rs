@@ -1,13 +1,40 @@
+use crate::odoo::auth::{decode_id_token_claims, OdooAuth, OidcUserinfo};
+use crate::odoo::domain::OdooDomain;
+use crate::odoo::error::{OdooClientError, OdooFault};
 use crate::odoo::types::*;
 use reqwest::{cookie::Jar, Client};
+use secrecy::{ExposeSecret, SecretString};
 use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+/// Credentials retained so an expired session can be silently renewed.
+struct StoredCredentials {
+  login: String,
+  password: SecretString,
+}
+
 pub struct OdooClient {
   client: Client,
   #[allow(dead_code)]
   jar: Arc<Jar>,
   base_url: String,
+  credentials: tokio::sync::RwLock<Option<StoredCredentials>>,
+  /// Bumped every time a relogin completes. Used as a single-flight guard:
+  /// a caller that hits an expired session only performs the relogin
+  /// itself if no one else has already renewed the session since it
+  /// observed the failure.
+  session_generation: AtomicU64,
+  relogin_lock: tokio::sync::Mutex<()>,
+  /// Active credential, if any, for requests that aren't establishing
+  /// auth themselves (i.e. everything but `login`). `None` until `login`,
+  /// `login_oidc`, or `login_oidc_bearer` has run once, or the client was
+  /// seeded via `with_session_token`.
+  auth: tokio::sync::RwLock<Option<OdooAuth>>,
+  /// Provider config used to `login_oidc_bearer`, retained so an expired
+  /// `OdooAuth::Bearer` token can be silently refreshed the same way an
+  /// expired session is silently re-logged-in.
+  oidc_config: tokio::sync::RwLock<Option<crate::odoo::oidc::OidcConfig>>,
 }
 
 impl OdooClient {
@@ -23,20 +50,129 @@ impl OdooClient {
       client,
       jar,
       base_url,
+      credentials: tokio::sync::RwLock::new(None),
+      session_generation: AtomicU64::new(0),
+      relogin_lock: tokio::sync::Mutex::new(()),
+      auth: tokio::sync::RwLock::new(None),
+      oidc_config: tokio::sync::RwLock::new(None),
+    }
+  }
+
+  /// Opt into transparent session renewal: if a CRUD call detects an
+  /// expired Odoo session, the client re-invokes `login` with these
+  /// credentials once and retries the original request before giving up.
+  /// Must be called before the client is shared across tasks (it's a
+  /// plain constructor-time setter, not a runtime toggle).
+  pub fn with_auto_relogin(self, login: String, password: SecretString) -> Self {
+    self
+      .credentials
+      .try_write()
+      .expect("with_auto_relogin must be called before the client is shared")
+      .replace(StoredCredentials { login, password });
+    self
+  }
+
+  /// Seed the client with an already-established Odoo session id (e.g.
+  /// one persisted by `foxia-cli login`), so it can make authenticated
+  /// requests without ever calling `login` itself. Must be called before
+  /// the client is shared across tasks (it's a plain constructor-time
+  /// setter, not a runtime toggle), mirroring `with_auto_relogin`.
+  pub fn with_session_token(self, session_id: String) -> Self {
+    self
+      .auth
+      .try_write()
+      .expect("with_session_token must be called before the client is shared")
+      .replace(OdooAuth::Session(session_id));
+    self
+  }
+
+  /// Re-login with the stored credentials, but only if no one else has
+  /// already done so since `observed_generation` was read by the caller —
+  /// this makes concurrent requests that all hit expiry trigger exactly
+  /// one re-login instead of a stampede.
+  async fn relogin_once(&self, observed_generation: u64) -> Result<(), OdooClientError> {
+    let _guard = self.relogin_lock.lock().await;
+
+    if self.session_generation.load(Ordering::SeqCst) != observed_generation {
+      // Someone else already renewed the session while we were waiting.
+      return Ok(());
+    }
+
+    let creds = self.credentials.read().await;
+    let Some(creds) = creds.as_ref() else {
+      return Err("auto_relogin requested but no credentials are stored".into());
+    };
+
+    self.login(creds.login.clone(), creds.password.clone()).await?;
+    self.session_generation.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+  }
+
+  async fn auto_relogin_enabled(&self) -> bool {
+    self.credentials.read().await.is_some()
+  }
+
+  /// Call after a CRUD request comes back with `error` set. If auto-relogin
+  /// is enabled and the error looks like an expired session, renews the
+  /// session (single-flighted against concurrent callers) and returns
+  /// `true` so the caller retries the original request once. Otherwise
+  /// returns `false` so the caller surfaces `error` as-is.
+  async fn try_recover_from_error(&self, error: &OdooError) -> bool {
+    if !OdooFault::classify(error).is_session_expired() || !self.auto_relogin_enabled().await {
+      return false;
     }
+
+    let observed_generation = self.session_generation.load(Ordering::SeqCst);
+    self.relogin_once(observed_generation).await.is_ok()
+  }
+
+  /// Returns the credential to attach to the next request, refreshing it
+  /// first if it's an expired `OdooAuth::Bearer` token and a refresh
+  /// token + provider config are on hand (set by `login_oidc_bearer`).
+  /// `Session` auth and a `Bearer` token without a refresh token are
+  /// returned as-is — the former is recovered by `try_recover_from_error`
+  /// instead, and the latter just fails the request once expired.
+  async fn current_auth(&self) -> Result<Option<OdooAuth>, OdooClientError> {
+    // Bind the read guard to a local and let it drop at the end of this
+    // statement instead of living through the whole `match` (temporary
+    // lifetime extension would otherwise keep it alive into the `_` arm,
+    // which used to call `self.auth.read().await` a second time and
+    // deadlock against a writer queued in between on this write-preferring
+    // lock).
+    let current = self.auth.read().await.clone();
+    let stale_refresh_token = match current.as_ref() {
+      Some(auth) if auth.is_expired() => match auth {
+        OdooAuth::Bearer { refresh_token: Some(rt), .. } => Some(rt.clone()),
+        _ => None,
+      },
+      _ => return Ok(current),
+    };
+
+    let (Some(refresh_token), Some(config)) = (stale_refresh_token, self.oidc_config.read().await.clone()) else {
+      return Ok(current);
+    };
+
+    let tokens = crate::odoo::oidc::refresh(&self.client, &config, &refresh_token).await?;
+    let refreshed = OdooAuth::Bearer {
+      access_token: tokens.access_token,
+      refresh_token: tokens.refresh_token.or(Some(refresh_token)),
+      expires_at: tokens.expires_at,
+    };
+    self.auth.write().await.replace(refreshed.clone());
+    Ok(Some(refreshed))
   }
 
   pub async fn login(
     &self,
     login: String,
-    password: String,
-  ) -> Result<OdooLoginResult, Box<dyn std::error::Error + Send + Sync>> {
+    password: secrecy::SecretString,
+  ) -> Result<OdooLoginResult, OdooClientError> {
     let url = format!("{}/res_users/login", self.base_url);
 
     let body = json!({
         "params": {
             "login": login,
-            "password": password,
+            "password": password.expose_secret(),
         }
     });
 
@@ -78,42 +214,119 @@ impl OdooClient {
     let res: OdooResponse<OdooLoginResult> = match serde_json::from_str(&text) {
       Ok(r) => r,
       Err(e) => {
-        return Err(format!("Failed to parse Odoo response: {}. Body: {}", e, text).into());
+        log::error!("Failed to parse Odoo login response: {}. Body: {}", e, text);
+        return Err(e.into());
       }
     };
 
-    if let Some(error) = res.error {
-      return Err(format!("Odoo login failed: {}", error.message).into());
+    let mut result = res.into_result()?;
+
+    // Prioritize session_id from cookie if not in body
+    if result.session_id.is_none() && session_id_from_cookie.is_some() {
+      log::info!("Using session_id from cookie since not in response body");
+      result.session_id = session_id_from_cookie;
     }
 
-    if let Some(mut result) = res.result {
-      // Prioritize session_id from cookie if not in body
-      if result.session_id.is_none() && session_id_from_cookie.is_some() {
-        log::info!("Using session_id from cookie since not in response body");
-        result.session_id = session_id_from_cookie;
-      }
+    if let Some(sid) = &result.session_id {
+      log::info!("Odoo session_id found: {}", sid);
+      self.auth.write().await.replace(OdooAuth::Session(sid.clone()));
+    } else {
+      log::warn!("No session_id found in response body or cookie!");
+    }
+    Ok(result)
+  }
+
+  /// Log in via an external OAuth2/OpenID Connect provider instead of
+  /// Odoo's own username/password form. Runs the PKCE authorization-code
+  /// flow (see `crate::odoo::oidc`) to obtain an id/access token, then
+  /// exchanges it against Odoo's OAuth signin endpoint so the existing
+  /// cookie jar captures `session_id` exactly as `login` does.
+  pub async fn login_oidc(
+    &self,
+    config: &crate::odoo::oidc::OidcConfig,
+    redirect_port: u16,
+  ) -> Result<OdooLoginResult, OdooClientError> {
+    let token = crate::odoo::oidc::authorize(&self.client, config, redirect_port).await?;
+
+    let url = format!("{}/auth_oauth/signin", self.base_url);
+    let body = json!({
+        "params": {
+            "provider": config.client_id,
+            "access_token": token,
+        }
+    });
+
+    let response = self.client.post(&url).json(&body).send().await?;
+    let text = response.text().await?;
 
-      if let Some(sid) = &result.session_id {
-        log::info!("Odoo session_id found: {}", sid);
-      } else {
-        log::warn!("No session_id found in response body or cookie!");
+    let res: OdooResponse<OdooLoginResult> = match serde_json::from_str(&text) {
+      Ok(r) => r,
+      Err(e) => {
+        log::error!("Failed to parse Odoo OIDC signin response: {}. Body: {}", e, text);
+        return Err(e.into());
       }
-      Ok(result)
-    } else {
-      Err("Odoo login returned no result".into())
+    };
+
+    let result = res.into_result()?;
+    if let Some(sid) = &result.session_id {
+      self.auth.write().await.replace(OdooAuth::Session(sid.clone()));
     }
+    Ok(result)
+  }
+
+  /// Authenticate against an Odoo instance that validates bearer tokens
+  /// directly (fronted by an OAuth2/OIDC provider) instead of going
+  /// through Odoo's own cookie-session login — unlike `login_oidc`, the
+  /// provider's token is never exchanged against Odoo's signin endpoint.
+  /// Runs the same PKCE authorization-code flow, then attaches the
+  /// resulting access token as an `Authorization: Bearer` header on every
+  /// subsequent request (refreshing it automatically once it expires, if
+  /// the provider issued a refresh token) and returns the claims decoded
+  /// from the ID token so the caller can show who's logged in.
+  pub async fn login_oidc_bearer(
+    &self,
+    config: &crate::odoo::oidc::OidcConfig,
+    redirect_port: u16,
+  ) -> Result<OidcUserinfo, OdooClientError> {
+    let tokens = crate::odoo::oidc::authorize_tokens(&self.client, config, redirect_port).await?;
+
+    let userinfo = tokens
+      .id_token
+      .as_deref()
+      .ok_or_else(|| OdooClientError::Other("OIDC provider did not return an ID token".to_string()))
+      .and_then(|id_token| decode_id_token_claims(id_token).map_err(OdooClientError::Other))?;
+
+    self.oidc_config.write().await.replace(config.clone());
+    self.auth.write().await.replace(OdooAuth::Bearer {
+      access_token: tokens.access_token,
+      refresh_token: tokens.refresh_token,
+      expires_at: tokens.expires_at,
+    });
+
+    Ok(userinfo)
   }
 
   pub async fn list_profiles(
     &self,
     offset: u32,
     limit: u32,
-  ) -> Result<OdooListResult, Box<dyn std::error::Error + Send + Sync>> {
+  ) -> Result<OdooListResult, OdooClientError> {
+    self.list_profiles_matching(OdooDomain::all(), offset, limit).await
+  }
+
+  /// Like `list_profiles`, but scoped to records matching `domain`
+  /// instead of unconditionally listing everything.
+  pub async fn list_profiles_matching(
+    &self,
+    domain: OdooDomain,
+    offset: u32,
+    limit: u32,
+  ) -> Result<OdooListResult, OdooClientError> {
     let url = format!("{}/api/hosotainguyen/list", self.base_url);
 
     let body = json!({
         "params": {
-            "domain": [],
+            "domain": domain.into_value(),
             "context2": {},
             "offset": offset,
             "limit": limit,
@@ -121,43 +334,66 @@ impl OdooClient {
         }
     });
 
-    let response = self.client.post(&url)
-            .header("accept", "application/json")
-            .header("content-type", "application/json;charset=UTF-8")
-            .header("user-agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) foxia_mkt/0.0.38 Chrome/138.0.7204.251 Electron/37.10.3 Safari/537.36")
-            .json(&body)
-            .send()
-            .await?;
+    for attempt in 0..2 {
+      let auth = self.current_auth().await?;
+      let mut request = self.client.post(&url)
+              .header("accept", "application/json")
+              .header("content-type", "application/json;charset=UTF-8")
+              .header("user-agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) foxia_mkt/0.0.38 Chrome/138.0.7204.251 Electron/37.10.3 Safari/537.36")
+              .json(&body);
+      if let Some(auth) = &auth {
+        request = auth.apply(request);
+      }
+      let response = request.send().await?;
 
-    let status = response.status();
-    let text = response.text().await?;
+      let status = response.status();
+      let text = response.text().await?;
 
-    // Cần in ra Terminal để debug nếu parse lỗi
-    log::info!("Odoo list_profiles response status: {}", status);
+      // Cần in ra Terminal để debug nếu parse lỗi
+      log::info!("Odoo list_profiles response status: {}", status);
 
-    let res: OdooResponse<OdooListResult> = match serde_json::from_str(&text) {
-      Ok(r) => r,
-      Err(e) => {
-        log::error!("Failed to parse Odoo list response: {}. Body: {}", e, text);
-        return Err(format!("Failed to parse Odoo list response: {}", e).into());
+      let res: OdooResponse<OdooListResult> = match serde_json::from_str(&text) {
+        Ok(r) => r,
+        Err(e) => {
+          log::error!("Failed to parse Odoo list response: {}. Body: {}", e, text);
+          return Err(e.into());
+        }
+      };
+
+      if let Some(error) = &res.error {
+        if attempt == 0 && self.try_recover_from_error(error).await {
+          continue;
+        }
       }
-    };
 
-    if let Some(error) = res.error {
-      return Err(format!("Odoo list profiles failed: {}", error.message).into());
+      return res.into_result();
     }
 
-    if let Some(result) = res.result {
-      Ok(result)
-    } else {
-      Err("Odoo list profiles returned no result".into())
-    }
+    unreachable!("loop either returns or retries exactly once")
   }
 
-  pub async fn create_profile(
+  /// Auto-paginating stream over `list_profiles_matching`, built on the
+  /// generic `pagination::paginate` helper. Keeps the `"order": "id desc"`
+  /// semantics `list_profiles` already uses.
+  pub fn profiles_stream(
     &self,
-    profile: OdooProfile,
-  ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+    domain: OdooDomain,
+    page_size: u32,
+  ) -> impl futures_core::Stream<Item = Result<OdooProfile, OdooClientError>> + '_ {
+    let params = OdooListParams {
+      domain,
+      context2: json!({}),
+      offset: 0,
+      limit: page_size,
+      order: "id desc".to_string(),
+    };
+
+    crate::odoo::pagination::paginate(params, Some(page_size), move |p| {
+      self.list_profiles_matching(p.domain, p.offset, p.limit)
+    })
+  }
+
+  pub async fn create_profile(&self, profile: OdooProfile) -> Result<serde_json::Value, OdooClientError> {
     let url = format!("{}/api/hosotainguyen/create", self.base_url);
 
     let body = json!({
@@ -169,57 +405,73 @@ impl OdooClient {
 
     log::info!("Odoo create_profile request to: {}, body: {}", url, body);
 
-    let response = self.client.post(&url).json(&body).send().await?;
-    let text = response.text().await?;
-    log::info!("Odoo create_profile response: {}", text);
+    for attempt in 0..2 {
+      let mut request = self.client.post(&url).json(&body);
+      if let Some(auth) = self.current_auth().await? {
+        request = auth.apply(request);
+      }
+      let response = request.send().await?;
+      let text = response.text().await?;
+      log::info!("Odoo create_profile response: {}", text);
 
-    let res: OdooResponse<serde_json::Value> = serde_json::from_str(&text)?;
+      let res: OdooResponse<serde_json::Value> = serde_json::from_str(&text)?;
+
+      if let Some(error) = &res.error {
+        if attempt == 0 && self.try_recover_from_error(error).await {
+          continue;
+        }
+      }
 
-    if let Some(error) = res.error {
-      return Err(format!("Odoo create profile failed: {}", error.message).into());
+      return res.into_result();
     }
 
-    Ok(res.result.unwrap_or(serde_json::Value::Null))
+    unreachable!("loop either returns or retries exactly once")
   }
 
-  pub async fn update_profile(
-    &self,
-    profile: OdooProfile,
-  ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+  pub async fn update_profile(&self, profile: OdooProfile) -> Result<serde_json::Value, OdooClientError> {
     let url = format!("{}/api/hosotainguyen/write", self.base_url);
 
+    let domain = OdooDomain::eq("id", profile.id.clone());
     let body = json!({
         "params": {
             "params": profile,
-            "domain": [["id", "=", profile.id]],
+            "domain": domain.into_value(),
             "context2": {},
         }
     });
 
     log::info!("Odoo update_profile request to: {}, body: {}", url, body);
 
-    let response = self.client.post(&url).json(&body).send().await?;
-    let text = response.text().await?;
-    log::info!("Odoo update_profile response: {}", text);
+    for attempt in 0..2 {
+      let mut request = self.client.post(&url).json(&body);
+      if let Some(auth) = self.current_auth().await? {
+        request = auth.apply(request);
+      }
+      let response = request.send().await?;
+      let text = response.text().await?;
+      log::info!("Odoo update_profile response: {}", text);
 
-    let res: OdooResponse<serde_json::Value> = serde_json::from_str(&text)?;
+      let res: OdooResponse<serde_json::Value> = serde_json::from_str(&text)?;
 
-    if let Some(error) = res.error {
-      return Err(format!("Odoo update profile failed: {}", error.message).into());
+      if let Some(error) = &res.error {
+        if attempt == 0 && self.try_recover_from_error(error).await {
+          continue;
+        }
+      }
+
+      return res.into_result();
     }
 
-    Ok(res.result.unwrap_or(serde_json::Value::Null))
+    unreachable!("loop either returns or retries exactly once")
   }
 
-  pub async fn delete_profile(
-    &self,
-    id: serde_json::Value,
-  ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+  pub async fn delete_profile(&self, id: serde_json::Value) -> Result<bool, OdooClientError> {
     let url = format!("{}/api/hosotainguyen/unlink", self.base_url);
 
+    let domain = OdooDomain::eq("id", id);
     let body = json!({
         "params": {
-            "domain": [["id", "=", id]],
+            "domain": domain.into_value(),
             "context2": {},
             "offset": 0,
             "limit": 1,
@@ -229,41 +481,54 @@ impl OdooClient {
 
     log::info!("Odoo delete_profile request to: {}, body: {}", url, body);
 
-    let response = self.client.post(&url).json(&body).send().await?;
-    let status = response.status();
-    let text = response.text().await?;
-
-    log::info!("Odoo delete_profile response status: {}, body: {}", status, text);
-
-    // Odoo có thể trả response không đúng format OdooResponse<bool>
-    match serde_json::from_str::<OdooResponse<bool>>(&text) {
-      Ok(res) => {
-        if let Some(error) = res.error {
-          return Err(format!("Odoo delete profile failed: {}", error.message).into());
-        }
-        Ok(res.result.unwrap_or(false))
+    for attempt in 0..2 {
+      let mut request = self.client.post(&url).json(&body);
+      if let Some(auth) = self.current_auth().await? {
+        request = auth.apply(request);
       }
-      Err(e) => {
-        log::warn!("Could not parse delete response as OdooResponse<bool>: {}. Trying OdooResponse<Value>...", e);
-        // Fallback: thử parse dạng khác, nếu status OK thì coi như thành công
-        match serde_json::from_str::<OdooResponse<serde_json::Value>>(&text) {
-          Ok(res) => {
-            if let Some(error) = res.error {
-              return Err(format!("Odoo delete profile failed: {}", error.message).into());
+      let response = request.send().await?;
+      let status = response.status();
+      let text = response.text().await?;
+
+      log::info!("Odoo delete_profile response status: {}, body: {}", status, text);
+
+      // Odoo có thể trả response không đúng format OdooResponse<bool>
+      match serde_json::from_str::<OdooResponse<bool>>(&text) {
+        Ok(res) => {
+          if let Some(error) = &res.error {
+            if attempt == 0 && self.try_recover_from_error(error).await {
+              continue;
             }
-            log::info!("Delete profile succeeded (parsed as Value): {:?}", res.result);
-            Ok(true)
           }
-          Err(_) => {
-            if status.is_success() {
-              log::warn!("Could not parse response but HTTP status is OK, assuming success");
-              Ok(true)
-            } else {
-              Err(format!("Failed to parse Odoo delete response: {}. Body: {}", e, text).into())
+          return res.into_result();
+        }
+        Err(e) => {
+          log::warn!("Could not parse delete response as OdooResponse<bool>: {}. Trying OdooResponse<Value>...", e);
+          // Fallback: thử parse dạng khác, nếu status OK thì coi như thành công
+          match serde_json::from_str::<OdooResponse<serde_json::Value>>(&text) {
+            Ok(res) => {
+              if let Some(error) = &res.error {
+                if attempt == 0 && self.try_recover_from_error(error).await {
+                  continue;
+                }
+              }
+              res.into_result()?;
+              log::info!("Delete profile succeeded (parsed as Value)");
+              return Ok(true);
+            }
+            Err(_) => {
+              return if status.is_success() {
+                log::warn!("Could not parse response but HTTP status is OK, assuming success");
+                Ok(true)
+              } else {
+                Err(format!("Failed to parse Odoo delete response: {}. Body: {}", e, text).into())
+              };
             }
           }
         }
       }
     }
+
+    unreachable!("loop either returns or retries exactly once")
   }
 }
@@ -1,5 +1,6 @@
 use crate::events;
 use crate::odoo::client::OdooClient;
+use crate::odoo::domain::OdooDomain;
 use crate::odoo::types::*;
 use crate::odoo::ODOO_CLIENT;
 use crate::profile::manager::ProfileManager;
@@ -14,12 +15,16 @@ pub async fn odoo_login(
 ) -> Result<OdooLoginResult, String> {
   let client = OdooClient::new(base_url);
   let result = client
-    .login(login, password)
+    .login(login.clone(), secrecy::SecretString::new(password.clone()))
     .await
     .map_err(|e| e.to_string())?;
 
   log::info!("Login successful! session_id: {:?}", result.session_id);
 
+  // Retain the credentials so a later CRUD call that hits an expired
+  // session can silently renew it instead of surfacing an auth error.
+  let client = client.with_auto_relogin(login, secrecy::SecretString::new(password));
+
   let mut odoo_client = ODOO_CLIENT.lock().await;
   *odoo_client = Some(client);
 
@@ -45,6 +50,23 @@ pub async fn upload_profile_to_odoo_s3(
 
   let profile_data_dir = profile.get_profile_data_path(&profiles_dir);
 
+  // If the profile has a sync macaroon, it was granted narrowly-scoped
+  // sync rights without the master Odoo session, so use it as the bearer
+  // credential instead. The chain is replayed and every caveat (profile
+  // scope, action, expiry) checked locally — an invalid or expired token
+  // is rejected before any network call is made.
+  let bearer_credential = match &profile.sync_macaroon {
+    Some(macaroon) => {
+      let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+      crate::macaroon::verify_sync_macaroon(macaroon, &profile_uuid, now).map_err(|e| e.to_string())?;
+      macaroon.clone()
+    }
+    None => session_id,
+  };
+
   // Create temp zip file with profile name instead of UUID
   let temp_dir = std::env::temp_dir().join("foxia-profile-sync");
   std::fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
@@ -60,7 +82,7 @@ pub async fn upload_profile_to_odoo_s3(
   }
 
   // Upload to S3
-  let profile_url = s3_transfer::upload_profile_to_s3(&base_url, &session_id, &zip_path)
+  let profile_url = s3_transfer::upload_profile_to_s3(&base_url, &bearer_credential, &zip_path)
     .await
     .map_err(|e| {
       log::error!("S3 upload error: {}", e);
@@ -160,6 +182,32 @@ pub async fn list_odoo_profiles(offset: u32, limit: u32) -> Result<OdooListResul
   }
 }
 
+/// Like `list_odoo_profiles`, but scoped by `filters`: `(field, operator,
+/// value)` leaves, ANDed together into a single `OdooDomain` before the
+/// request is made.
+#[tauri::command]
+pub async fn list_odoo_profiles_matching(
+  filters: Vec<(String, String, serde_json::Value)>,
+  offset: u32,
+  limit: u32,
+) -> Result<OdooListResult, String> {
+  let odoo_client = ODOO_CLIENT.lock().await;
+  let Some(client) = odoo_client.as_ref() else {
+    return Err("Not logged in to Odoo".to_string());
+  };
+
+  let domain = filters
+    .into_iter()
+    .fold(OdooDomain::all(), |acc, (field, operator, value)| {
+      acc.and(OdooDomain::leaf(field, &operator, value))
+    });
+
+  client
+    .list_profiles_matching(domain, offset, limit)
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn create_odoo_profile(profile: OdooProfile) -> Result<serde_json::Value, String> {
   let odoo_client = ODOO_CLIENT.lock().await;
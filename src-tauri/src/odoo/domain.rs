@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A single, self-contained Odoo domain: Odoo's own "polish notation"
+/// filter list (leaf triples plus `&`/`|`/`!` operators), wrapped so
+/// callers build and combine domains without hand-writing the prefix
+/// bookkeeping those operators require. The combinators below mirror
+/// Odoo's own `expression.AND`/`OR`/`normalize_domain` helpers rather
+/// than inventing new semantics, since whatever gets sent over JSON-RPC
+/// still has to parse as a domain the Odoo server already understands.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OdooDomain(Vec<serde_json::Value>);
+
+impl OdooDomain {
+  /// The domain that matches every record, i.e. `"domain": []`.
+  pub fn all() -> Self {
+    Self(Vec::new())
+  }
+
+  /// A single `[field, operator, value]` leaf for an operator without its
+  /// own named helper below (e.g. Odoo's `child_of`, `=like`, ...).
+  pub fn leaf(field: impl Into<String>, operator: &str, value: impl Into<serde_json::Value>) -> Self {
+    Self(vec![json!([field.into(), operator, value.into()])])
+  }
+
+  pub fn eq(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "=", value)
+  }
+
+  pub fn ne(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "!=", value)
+  }
+
+  pub fn gt(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, ">", value)
+  }
+
+  pub fn gte(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, ">=", value)
+  }
+
+  pub fn lt(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "<", value)
+  }
+
+  pub fn lte(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "<=", value)
+  }
+
+  pub fn like(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "like", value)
+  }
+
+  pub fn ilike(field: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+    Self::leaf(field, "ilike", value)
+  }
+
+  pub fn in_list(field: impl Into<String>, values: Vec<serde_json::Value>) -> Self {
+    Self::leaf(field, "in", json!(values))
+  }
+
+  pub fn not_in_list(field: impl Into<String>, values: Vec<serde_json::Value>) -> Self {
+    Self::leaf(field, "not in", json!(values))
+  }
+
+  /// `self AND other`. An empty side (`OdooDomain::all()`) contributes no
+  /// filter rather than an invalid empty operand.
+  pub fn and(self, other: Self) -> Self {
+    Self(combine("&", vec![self.0, other.0]))
+  }
+
+  /// `self OR other`.
+  pub fn or(self, other: Self) -> Self {
+    Self(combine("|", vec![self.0, other.0]))
+  }
+
+  /// Negate the whole domain. `!OdooDomain::all()` stays empty: there's
+  /// no Odoo-domain encoding of "matches nothing", and a negated no-op
+  /// filter is never what a caller building one actually wants.
+  pub fn not(self) -> Self {
+    Self(negate(self.0))
+  }
+
+  /// The flat polish-notation list, ready to drop into a JSON-RPC
+  /// `"domain"` param.
+  pub fn into_value(self) -> Vec<serde_json::Value> {
+    self.0
+  }
+}
+
+/// Combine `domains` with `op` (`"&"` or `"|"`), following the same
+/// prefix-counting Odoo's own `expression.AND`/`OR` use: `op` repeated
+/// `domains.len() - 1` times, then every domain's terms concatenated.
+/// Empty operands (no filter) are dropped first so ANDing/ORing against
+/// `OdooDomain::all()` is a no-op instead of producing a malformed domain.
+fn combine(op: &'static str, domains: Vec<Vec<serde_json::Value>>) -> Vec<serde_json::Value> {
+  let mut domains: Vec<_> = domains.into_iter().filter(|d| !d.is_empty()).collect();
+  match domains.len() {
+    0 => Vec::new(),
+    1 => domains.remove(0),
+    len => {
+      let mut result = vec![json!(op); len - 1];
+      for d in domains {
+        result.extend(d);
+      }
+      result
+    }
+  }
+}
+
+/// Negate `domain`. `combine` already leaves every domain it produces
+/// self-contained and correctly prefixed (a single operand, however many
+/// leaves it has), so negating is just prepending one `!` - no extra
+/// operators needed, even when `domain.len() > 1`.
+fn negate(domain: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+  if domain.is_empty() {
+    return Vec::new();
+  }
+  let mut result = vec![json!("!")];
+  result.extend(domain);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn not_of_single_leaf() {
+    let domain = OdooDomain::eq("a", "1").not();
+    assert_eq!(domain.into_value(), vec![json!("!"), json!(["a", "=", "1"])]);
+  }
+
+  #[test]
+  fn not_of_and_does_not_duplicate_operators() {
+    let domain = OdooDomain::eq("a", "1").and(OdooDomain::eq("b", "2")).not();
+    assert_eq!(
+      domain.into_value(),
+      vec![
+        json!("!"),
+        json!("&"),
+        json!(["a", "=", "1"]),
+        json!(["b", "=", "2"]),
+      ]
+    );
+  }
+}
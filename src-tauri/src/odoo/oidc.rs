@@ -0,0 +1,244 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Configuration for logging into an Odoo instance that delegates
+/// authentication to an external OAuth2/OpenID Connect provider
+/// (Google, Keycloak, etc.) instead of classic username/password.
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+  pub issuer_url: String,
+  pub client_id: String,
+  pub client_secret: Option<String>,
+  pub scopes: Vec<String>,
+}
+
+impl OidcConfig {
+  pub fn new(issuer_url: impl Into<String>, client_id: impl Into<String>) -> Self {
+    Self {
+      issuer_url: issuer_url.into(),
+      client_id: client_id.into(),
+      client_secret: None,
+      scopes: vec!["openid".to_string(), "email".to_string()],
+    }
+  }
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+  authorization_endpoint: String,
+  token_endpoint: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+  access_token: String,
+  id_token: Option<String>,
+  refresh_token: Option<String>,
+  expires_in: Option<i64>,
+  #[allow(dead_code)]
+  token_type: Option<String>,
+}
+
+/// Tokens obtained from an OIDC provider's token endpoint: an access
+/// token for calling APIs fronted by that provider, optionally a refresh
+/// token for renewing it once `expires_at` has passed, and (if the
+/// provider issued one) the ID token carrying the user's claims.
+#[derive(Debug, Clone)]
+pub struct OidcTokens {
+  pub access_token: String,
+  pub refresh_token: Option<String>,
+  pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+  pub id_token: Option<String>,
+}
+
+impl From<TokenResponse> for OidcTokens {
+  fn from(token: TokenResponse) -> Self {
+    Self {
+      access_token: token.access_token,
+      refresh_token: token.refresh_token,
+      expires_at: token
+        .expires_in
+        .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs)),
+      id_token: token.id_token,
+    }
+  }
+}
+
+/// A PKCE code verifier + its derived S256 code challenge.
+struct Pkce {
+  verifier: String,
+  challenge: String,
+}
+
+fn generate_pkce() -> Pkce {
+  let mut bytes = [0u8; 32];
+  rand::thread_rng().fill_bytes(&mut bytes);
+  let verifier = URL_SAFE_NO_PAD.encode(bytes);
+
+  let mut hasher = Sha256::new();
+  hasher.update(verifier.as_bytes());
+  let challenge = URL_SAFE_NO_PAD.encode(hasher.finalize());
+
+  Pkce { verifier, challenge }
+}
+
+async fn fetch_discovery_document(
+  http: &reqwest::Client,
+  issuer_url: &str,
+) -> Result<DiscoveryDocument, Box<dyn std::error::Error + Send + Sync>> {
+  let url = format!(
+    "{}/.well-known/openid-configuration",
+    issuer_url.trim_end_matches('/')
+  );
+  Ok(http.get(url).send().await?.json().await?)
+}
+
+/// Open the given URL in the user's default browser.
+fn open_authorization_url(url: &str) -> std::io::Result<()> {
+  #[cfg(target_os = "macos")]
+  return std::process::Command::new("open").arg(url).status().map(|_| ());
+  #[cfg(target_os = "linux")]
+  return std::process::Command::new("xdg-open")
+    .arg(url)
+    .status()
+    .map(|_| ());
+  #[cfg(target_os = "windows")]
+  return std::process::Command::new("cmd")
+    .args(["/C", "start", "", url])
+    .status()
+    .map(|_| ());
+}
+
+/// Spin up a one-shot localhost HTTP listener to catch the provider's
+/// `?code=...` redirect, returning the authorization code once a request
+/// arrives.
+async fn capture_redirect_code(
+  port: u16,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+  let (mut socket, _) = listener.accept().await?;
+
+  let mut buf = [0u8; 4096];
+  let n = socket.read(&mut buf).await?;
+  let request = String::from_utf8_lossy(&buf[..n]);
+
+  let request_line = request.lines().next().unwrap_or_default();
+  let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+  let query: HashMap<String, String> = path
+    .split_once('?')
+    .map(|(_, q)| q)
+    .unwrap_or_default()
+    .split('&')
+    .filter_map(|pair| pair.split_once('='))
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+
+  let response = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n\
+    <html><body>Login complete, you can close this tab.</body></html>";
+  socket.write_all(response.as_bytes()).await?;
+
+  query
+    .get("code")
+    .cloned()
+    .ok_or_else(|| "Redirect did not include an authorization code".into())
+}
+
+/// Runs the authorization-code-with-PKCE flow against the configured
+/// provider and returns the raw token response.
+async fn run_authorization_code_flow(
+  http: &reqwest::Client,
+  config: &OidcConfig,
+  redirect_port: u16,
+) -> Result<TokenResponse, Box<dyn std::error::Error + Send + Sync>> {
+  let discovery = fetch_discovery_document(http, &config.issuer_url).await?;
+  let pkce = generate_pkce();
+  let redirect_uri = format!("http://127.0.0.1:{}/callback", redirect_port);
+
+  let scopes = config.scopes.join(" ");
+  let auth_url = format!(
+    "{}?response_type=code&client_id={}&redirect_uri={}&scope={}&code_challenge={}&code_challenge_method=S256",
+    discovery.authorization_endpoint,
+    urlencoding::encode(&config.client_id),
+    urlencoding::encode(&redirect_uri),
+    urlencoding::encode(&scopes),
+    pkce.challenge,
+  );
+
+  open_authorization_url(&auth_url)?;
+  let code = capture_redirect_code(redirect_port).await?;
+
+  let mut params = vec![
+    ("grant_type", "authorization_code"),
+    ("code", code.as_str()),
+    ("redirect_uri", redirect_uri.as_str()),
+    ("client_id", config.client_id.as_str()),
+    ("code_verifier", pkce.verifier.as_str()),
+  ];
+  if let Some(secret) = &config.client_secret {
+    params.push(("client_secret", secret.as_str()));
+  }
+
+  Ok(
+    http
+      .post(&discovery.token_endpoint)
+      .form(&params)
+      .send()
+      .await?
+      .json()
+      .await?,
+  )
+}
+
+/// Run the authorization-code-with-PKCE flow against the configured
+/// provider and return the `id_token` (falling back to `access_token` if
+/// the provider doesn't issue one) to hand to Odoo's OAuth signin.
+pub async fn authorize(
+  http: &reqwest::Client,
+  config: &OidcConfig,
+  redirect_port: u16,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+  let token = run_authorization_code_flow(http, config, redirect_port).await?;
+  Ok(token.id_token.unwrap_or(token.access_token))
+}
+
+/// Like `authorize`, but for Odoo instances that validate bearer tokens
+/// themselves instead of consuming the ID token via Odoo's own signin
+/// endpoint — returns the full token set (access/refresh/expiry/id_token)
+/// instead of collapsing it into one string.
+pub async fn authorize_tokens(
+  http: &reqwest::Client,
+  config: &OidcConfig,
+  redirect_port: u16,
+) -> Result<OidcTokens, Box<dyn std::error::Error + Send + Sync>> {
+  Ok(run_authorization_code_flow(http, config, redirect_port).await?.into())
+}
+
+/// Exchanges a refresh token for a new access token, e.g. once
+/// `OidcTokens::expires_at` has passed.
+pub async fn refresh(
+  http: &reqwest::Client,
+  config: &OidcConfig,
+  refresh_token: &str,
+) -> Result<OidcTokens, Box<dyn std::error::Error + Send + Sync>> {
+  let discovery = fetch_discovery_document(http, &config.issuer_url).await?;
+
+  let mut params = vec![
+    ("grant_type", "refresh_token"),
+    ("refresh_token", refresh_token),
+    ("client_id", config.client_id.as_str()),
+  ];
+  if let Some(secret) = &config.client_secret {
+    params.push(("client_secret", secret.as_str()));
+  }
+
+  let token: TokenResponse = http.post(&discovery.token_endpoint).form(&params).send().await?.json().await?;
+  Ok(token.into())
+}
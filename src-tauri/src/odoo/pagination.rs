@@ -0,0 +1,65 @@
+use crate::odoo::error::OdooClientError;
+use crate::odoo::types::{OdooListParams, OdooListResult, OdooProfile};
+use futures_core::Stream;
+use futures_util::StreamExt;
+use std::future::Future;
+
+/// Auto-paginating stream over any `OdooListResult`-returning endpoint,
+/// analogous to the page iterators octocrab/libstripe build over their own
+/// list endpoints. Starting from `params`, `fetch` is called once per page
+/// with `offset`/`limit` advanced to the next page; the stream stops once
+/// the accumulated item count reaches `total_count` or a page comes back
+/// shorter than the requested page size — a short page always wins, even
+/// if `total_count` disagrees, so a dataset that shrinks mid-stream can't
+/// make this loop forever.
+pub fn paginate<F, Fut>(
+  params: OdooListParams,
+  page_size: Option<u32>,
+  fetch: F,
+) -> impl Stream<Item = Result<OdooProfile, OdooClientError>>
+where
+  F: Fn(OdooListParams) -> Fut,
+  Fut: Future<Output = Result<OdooListResult, OdooClientError>>,
+{
+  let limit = page_size.unwrap_or(params.limit).max(1);
+
+  async_stream::try_stream! {
+    let mut offset = params.offset;
+    let mut fetched: i64 = 0;
+
+    loop {
+      let page_params = OdooListParams {
+        offset,
+        limit,
+        ..params.clone()
+      };
+      let page = fetch(page_params).await?;
+      let page_len = page.items.len() as u32;
+
+      for profile in page.items {
+        yield profile;
+      }
+
+      fetched += page_len as i64;
+      offset += page_len;
+
+      if page_len == 0 || page_len < limit || fetched >= page.total_count as i64 {
+        break;
+      }
+    }
+  }
+}
+
+/// Convenience wrapper around `paginate` for callers that want the whole
+/// result set at once instead of incremental delivery.
+pub async fn collect_all<F, Fut>(
+  params: OdooListParams,
+  page_size: Option<u32>,
+  fetch: F,
+) -> Result<Vec<OdooProfile>, OdooClientError>
+where
+  F: Fn(OdooListParams) -> Fut,
+  Fut: Future<Output = Result<OdooListResult, OdooClientError>>,
+{
+  paginate(params, page_size, fetch).collect::<Vec<_>>().await.into_iter().collect()
+}
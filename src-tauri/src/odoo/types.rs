@@ -1,10 +1,74 @@
+use crate::odoo::domain::OdooDomain;
+use crate::odoo::error::{OdooClientError, OdooFault};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Wire representation of `OdooProxy.port`: Odoo emits this as either a
+/// JSON number or the same value stringified, depending on the endpoint.
+/// Exists only to drive `deserialize_odoo_port` — the field itself is a
+/// plain `u16` once decoded.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OdooPortValue {
+  Number(u16),
+  Text(String),
+}
+
+fn deserialize_odoo_port<'de, D>(deserializer: D) -> Result<u16, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  match OdooPortValue::deserialize(deserializer)? {
+    OdooPortValue::Number(n) => Ok(n),
+    OdooPortValue::Text(s) => s.parse::<u16>().map_err(serde::de::Error::custom),
+  }
+}
+
+/// Parses an Odoo timestamp field, accepting both Odoo's own
+/// `"%Y-%m-%d %H:%M:%S"` (naive UTC) format and RFC3339. Any other shape,
+/// including a malformed string, degrades to `None` instead of failing
+/// the whole profile decode.
+fn deserialize_odoo_datetime<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let raw = Option::<serde_json::Value>::deserialize(deserializer)?;
+  Ok(raw.and_then(|v| match v {
+    serde_json::Value::String(s) => parse_odoo_datetime(&s),
+    _ => None,
+  }))
+}
+
+fn parse_odoo_datetime(s: &str) -> Option<DateTime<Utc>> {
+  if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+    return Some(dt.with_timezone(&Utc));
+  }
+  NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+    .ok()
+    .map(|naive| naive.and_utc())
+}
+
+/// Deserializes a JSON field as a `String`, but degrades to `None` (rather
+/// than failing the whole profile decode) if Odoo sends something other
+/// than a string — seen in practice for fields some custom Odoo modules
+/// leave `false` instead of omitting.
+fn deserialize_lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let raw = Option::<serde_json::Value>::deserialize(deserializer)?;
+  Ok(raw.and_then(|v| match v {
+    serde_json::Value::String(s) => Some(s),
+    _ => None,
+  }))
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OdooProxy {
   pub giaothuc: String,
   pub ip: String,
-  pub port: serde_json::Value,
+  #[serde(deserialize_with = "deserialize_odoo_port")]
+  pub port: u16,
   pub tendangnhap: Option<String>,
   pub matkhau: Option<String>,
 }
@@ -13,24 +77,34 @@ pub struct OdooProxy {
 pub struct OdooProfile {
   pub id: serde_json::Value,
   pub name: String,
-  #[serde(rename = "userAgent", skip_serializing_if = "Option::is_none")]
-  pub user_agent: Option<serde_json::Value>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub timezone: Option<serde_json::Value>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub language: Option<serde_json::Value>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub platform: Option<serde_json::Value>,
+  #[serde(
+    rename = "userAgent",
+    default,
+    deserialize_with = "deserialize_lenient_string",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub user_agent: Option<String>,
+  #[serde(default, deserialize_with = "deserialize_lenient_string", skip_serializing_if = "Option::is_none")]
+  pub timezone: Option<String>,
+  #[serde(default, deserialize_with = "deserialize_lenient_string", skip_serializing_if = "Option::is_none")]
+  pub language: Option<String>,
+  #[serde(default, deserialize_with = "deserialize_lenient_string", skip_serializing_if = "Option::is_none")]
+  pub platform: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub proxy_ids: Option<Vec<OdooProxy>>,
   #[serde(rename = "profileUrl", skip_serializing_if = "Option::is_none")]
   pub profile_url: Option<serde_json::Value>,
   #[serde(rename = "localPath", skip_serializing_if = "Option::is_none")]
   pub local_path: Option<serde_json::Value>,
-  #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
-  pub created_at: Option<serde_json::Value>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub create_date: Option<serde_json::Value>,
+  #[serde(
+    rename = "createdAt",
+    default,
+    deserialize_with = "deserialize_odoo_datetime",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub created_at: Option<DateTime<Utc>>,
+  #[serde(default, deserialize_with = "deserialize_odoo_datetime", skip_serializing_if = "Option::is_none")]
+  pub create_date: Option<DateTime<Utc>>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub username: Option<serde_json::Value>,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -47,6 +121,20 @@ pub struct OdooResponse<T> {
   pub error: Option<OdooError>,
 }
 
+impl<T> OdooResponse<T> {
+  /// Collapses the `result`/`error` pair JSON-RPC responses carry into a
+  /// single `Result`, classifying `error` via `OdooFault::classify` so
+  /// callers can `?` instead of matching both `Option`s by hand.
+  pub fn into_result(self) -> Result<T, OdooClientError> {
+    if let Some(error) = self.error {
+      return Err(OdooFault::classify(&error).into());
+    }
+    self
+      .result
+      .ok_or_else(|| OdooClientError::Other("Odoo response had no result and no error".into()))
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OdooError {
   pub code: i32,
@@ -77,10 +165,9 @@ pub struct OdooParams<T> {
   pub params: T,
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OdooListParams {
-  pub domain: Vec<Vec<serde_json::Value>>,
+  pub domain: OdooDomain,
   pub context2: serde_json::Value,
   pub offset: u32,
   pub limit: u32,
@@ -0,0 +1,97 @@
+use crate::camoufox_manager::CamoufoxConfig;
+use crate::profile::manager::ProfileManager;
+use serde::Serialize;
+
+/// Per-item outcome of a batch profile operation, so a caller that
+/// selected dozens of profiles can report exactly which ones succeeded
+/// and why the rest didn't, instead of the whole batch failing on the
+/// first error the way a single `Result`-returning command would.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItemResult {
+  pub profile_id: String,
+  pub ok: bool,
+  pub error: Option<String>,
+}
+
+impl BatchItemResult {
+  fn ok(profile_id: String) -> Self {
+    Self {
+      profile_id,
+      ok: true,
+      error: None,
+    }
+  }
+
+  fn err(profile_id: String, error: impl ToString) -> Self {
+    Self {
+      profile_id,
+      ok: false,
+      error: Some(error.to_string()),
+    }
+  }
+}
+
+impl ProfileManager {
+  /// Clone each profile in `profile_ids`, continuing past failures (e.g.
+  /// an id that no longer exists) rather than aborting the whole batch.
+  pub fn clone_profiles_batch(&self, profile_ids: Vec<String>) -> Vec<BatchItemResult> {
+    profile_ids
+      .into_iter()
+      .map(|id| match self.clone_profile(&id) {
+        Ok(_) => BatchItemResult::ok(id),
+        Err(e) => BatchItemResult::err(id, e),
+      })
+      .collect()
+  }
+
+  /// Delete each profile in `profile_ids`, continuing past failures.
+  pub fn delete_profiles_batch(&self, app_handle: &tauri::AppHandle, profile_ids: Vec<String>) -> Vec<BatchItemResult> {
+    profile_ids
+      .into_iter()
+      .map(|id| match self.delete_profile(app_handle, &id) {
+        Ok(()) => BatchItemResult::ok(id),
+        Err(e) => BatchItemResult::err(id, e),
+      })
+      .collect()
+  }
+
+  /// Apply a (possibly different) `CamoufoxConfig` to each `(profile_id,
+  /// config)` pair, continuing past failures — e.g. one profile's config
+  /// pointing at a missing executable shouldn't block the rest from
+  /// updating.
+  pub async fn update_camoufox_config_batch(
+    &self,
+    app_handle: tauri::AppHandle,
+    items: Vec<(String, CamoufoxConfig)>,
+  ) -> Vec<BatchItemResult> {
+    let mut results = Vec::with_capacity(items.len());
+    for (id, config) in items {
+      let result = self.update_camoufox_config(app_handle.clone(), &id, config).await;
+      results.push(match result {
+        Ok(()) => BatchItemResult::ok(id),
+        Err(e) => BatchItemResult::err(id, e),
+      });
+    }
+    results
+  }
+}
+
+#[tauri::command]
+pub fn clone_profiles_batch(profile_ids: Vec<String>) -> Vec<BatchItemResult> {
+  ProfileManager::instance().clone_profiles_batch(profile_ids)
+}
+
+#[tauri::command]
+pub fn delete_profiles_batch(app_handle: tauri::AppHandle, profile_ids: Vec<String>) -> Vec<BatchItemResult> {
+  ProfileManager::instance().delete_profiles_batch(&app_handle, profile_ids)
+}
+
+#[tauri::command]
+pub async fn update_camoufox_config_batch(
+  app_handle: tauri::AppHandle,
+  items: Vec<(String, CamoufoxConfig)>,
+) -> Vec<BatchItemResult> {
+  ProfileManager::instance()
+    .update_camoufox_config_batch(app_handle, items)
+    .await
+}
@@ -0,0 +1,237 @@
+use crate::profile::manager::ProfileManager;
+use crate::profile::types::BrowserProfile;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Bumped whenever the archive layout or `manifest.json` shape changes.
+/// `import_profile_bundle` rejects anything newer than it understands
+/// instead of guessing.
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleManifest {
+  format_version: u32,
+  origin: String,
+  /// Original (pre-export) profile UUIDs, in archive order — the path
+  /// prefix each profile's files were written under.
+  profiles: Vec<String>,
+}
+
+impl ProfileManager {
+  /// Package one profile's `metadata.json` (which embeds its generated
+  /// fingerprint config) and `profile/` data directory into a standalone
+  /// zip. Thin wrapper over `export_profiles` for the common
+  /// single-profile case.
+  pub fn export_profile(&self, profile_id: &str, dest: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    self.export_profiles(std::slice::from_ref(&profile_id.to_string()), dest)
+  }
+
+  /// Package multiple profiles into a single combined archive, so a
+  /// batch of profiles can be moved between machines in one file.
+  pub fn export_profiles(&self, profile_ids: &[String], dest: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let profiles_dir = self.get_profiles_dir();
+    let all_profiles = self.list_profiles()?;
+
+    let file = fs::File::create(dest)?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut included = Vec::new();
+    for profile_id in profile_ids {
+      let uuid = uuid::Uuid::parse_str(profile_id)?;
+      let profile = all_profiles
+        .iter()
+        .find(|p| p.id == uuid)
+        .ok_or_else(|| format!("Profile {} not found", profile_id))?;
+
+      let profile_uuid_dir = profiles_dir.join(profile.id.to_string());
+      let metadata = fs::read(profile_uuid_dir.join("metadata.json"))?;
+      zip.start_file(format!("{}/metadata.json", profile.id), options)?;
+      zip.write_all(&metadata)?;
+
+      add_dir_to_zip(
+        &mut zip,
+        &profile_uuid_dir.join("profile"),
+        &format!("{}/profile", profile.id),
+        options,
+      )?;
+
+      included.push(profile.id.to_string());
+    }
+
+    let manifest = BundleManifest {
+      format_version: BUNDLE_FORMAT_VERSION,
+      origin: "foxia".to_string(),
+      profiles: included,
+    };
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    zip.finish()?;
+    Ok(dest.to_path_buf())
+  }
+
+  /// Restore every profile in a bundle produced by `export_profile(s)`
+  /// under a freshly minted UUID (so re-importing on the same machine
+  /// never collides with the original), skipping any profile that dedups
+  /// against an existing one by `odoo_id` — the same rule
+  /// `import_zsmkt_profiles_batch` uses. Emits `profiles-changed` once if
+  /// anything was imported.
+  pub fn import_profile_bundle(&self, src: &Path) -> Result<Vec<BrowserProfile>, Box<dyn std::error::Error>> {
+    let file = fs::File::open(src)?;
+    let mut archive = ZipArchive::new(file)?;
+
+    let manifest: BundleManifest = {
+      let mut entry = archive.by_name("manifest.json")?;
+      let mut contents = String::new();
+      entry.read_to_string(&mut contents)?;
+      serde_json::from_str(&contents)?
+    };
+
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+      return Err(format!("Bundle format version {} is newer than supported", manifest.format_version).into());
+    }
+
+    let existing_profiles = self.list_profiles()?;
+    let mut imported = Vec::new();
+
+    for original_id in &manifest.profiles {
+      let mut metadata_contents = String::new();
+      archive
+        .by_name(&format!("{}/metadata.json", original_id))?
+        .read_to_string(&mut metadata_contents)?;
+      let mut profile: BrowserProfile = serde_json::from_str(&metadata_contents)?;
+
+      let already_exists = existing_profiles
+        .iter()
+        .any(|p| p.odoo_id.is_some() && profile.odoo_id.is_some() && p.odoo_id == profile.odoo_id);
+      if already_exists {
+        log::info!(
+          "Profile '{}' (odoo_id: {:?}) already exists, skipping import",
+          profile.name,
+          profile.odoo_id
+        );
+        continue;
+      }
+
+      let new_id = uuid::Uuid::new_v4();
+      profile.id = new_id;
+      profile.process_id = None;
+      profile.last_launch = None;
+      profile.automation = None;
+      profile.attached_port = None;
+
+      let profile_uuid_dir = self.get_profiles_dir().join(new_id.to_string());
+      let profile_data_dir = profile_uuid_dir.join("profile");
+      fs::create_dir_all(&profile_data_dir)?;
+
+      let data_prefix = format!("{}/profile/", original_id);
+      for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(rel_path) = entry.name().strip_prefix(data_prefix.as_str()) else {
+          continue;
+        };
+        if rel_path.is_empty() || entry.is_dir() {
+          continue;
+        }
+        if !is_safe_rel_path(Path::new(rel_path)) {
+          log::warn!("Skipping zip entry with unsafe path traversal: {}", rel_path);
+          continue;
+        }
+        let Some(out_path) = confine_to_dir(&profile_data_dir, rel_path)? else {
+          log::warn!("Skipping zip entry that escapes its extraction directory: {}", rel_path);
+          continue;
+        };
+        let mut out_file = fs::File::create(&out_path)?;
+        std::io::copy(&mut entry, &mut out_file)?;
+      }
+
+      self.save_profile(&profile)?;
+      imported.push(profile);
+    }
+
+    if !imported.is_empty() {
+      let _ = crate::events::emit_empty("profiles-changed");
+    }
+    Ok(imported)
+  }
+}
+
+/// Whether a zip entry's path (with its known archive prefix already
+/// stripped) looks safe to join onto an extraction directory: no `..`
+/// traversal and no absolute-path component (`Path::join` silently
+/// discards its base when the right-hand side is absolute, e.g.
+/// `out_dir.join("/etc/cron.d/evil")` yields `/etc/cron.d/evil`). This is
+/// a first-pass filter only - `confine_to_dir` does the actual
+/// containment check against the resolved filesystem path.
+fn is_safe_rel_path(rel_path: &Path) -> bool {
+  use std::path::Component;
+  rel_path
+    .components()
+    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Join `rel_path` onto `out_dir` and verify the result actually resolves
+/// inside `out_dir`, rather than trusting `is_safe_rel_path`'s
+/// component-level check alone (e.g. a symlinked intermediate directory
+/// could still escape it). Creates `out_dir` and any missing parent
+/// directories for `rel_path` as a side effect, since `canonicalize`
+/// requires the path to exist. Returns `None` if the resolved path
+/// escapes `out_dir`.
+fn confine_to_dir(out_dir: &Path, rel_path: &str) -> std::io::Result<Option<PathBuf>> {
+  let out_path = out_dir.join(rel_path);
+  let parent = out_path.parent().unwrap_or(out_dir);
+  fs::create_dir_all(parent)?;
+
+  let canonical_out_dir = fs::canonicalize(out_dir)?;
+  let canonical_parent = fs::canonicalize(parent)?;
+  if !canonical_parent.starts_with(&canonical_out_dir) {
+    return Ok(None);
+  }
+
+  Ok(Some(out_path))
+}
+
+/// Recursively add every file under `dir` to `zip`, rooted at `zip_prefix`.
+fn add_dir_to_zip(
+  zip: &mut ZipWriter<fs::File>,
+  dir: &Path,
+  zip_prefix: &str,
+  options: FileOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+  if !dir.exists() {
+    return Ok(());
+  }
+  for entry in fs::read_dir(dir)? {
+    let entry = entry?;
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().into_owned();
+    let zip_path = format!("{}/{}", zip_prefix, name);
+    if path.is_dir() {
+      add_dir_to_zip(zip, &path, &zip_path, options)?;
+    } else {
+      zip.start_file(&zip_path, options)?;
+      zip.write_all(&fs::read(&path)?)?;
+    }
+  }
+  Ok(())
+}
+
+#[tauri::command]
+pub fn export_profile(profile_ids: Vec<String>, dest: String) -> Result<String, String> {
+  ProfileManager::instance()
+    .export_profiles(&profile_ids, Path::new(&dest))
+    .map(|path| path.to_string_lossy().into_owned())
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn import_profile_bundle(path: String) -> Result<Vec<BrowserProfile>, String> {
+  ProfileManager::instance()
+    .import_profile_bundle(Path::new(&path))
+    .map_err(|e| e.to_string())
+}
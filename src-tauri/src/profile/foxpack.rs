@@ -0,0 +1,496 @@
+use crate::browser::ProxySettings;
+use crate::camoufox_manager::CamoufoxConfig;
+use crate::profile::manager::ProfileManager;
+use crate::profile::prefs::{PrefStore, PrefValue};
+use crate::profile::signing::{self, SignatureBlock};
+use crate::profile::types::BrowserProfile;
+use crate::wayfern_manager::WayfernConfig;
+use aes_gcm::aead::{Aead, KeyInit, OsRng as AesOsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Bumped whenever `manifest.json`'s shape changes. `import_profile_pack`
+/// rejects anything newer than it understands.
+const FOXPACK_FORMAT_VERSION: u32 = 1;
+
+/// Data that's copied verbatim into the new profile's data directory on
+/// import: cookies, localStorage, IndexedDB, installed extensions.
+const OVERRIDES_PREFIX: &str = "overrides/";
+
+/// Data that should only apply when the profile actually runs, rather
+/// than at import time (e.g. a launch-time overlay a future runner step
+/// merges in) — kept in its own `client-overrides/` directory beside
+/// `profile/` in the profile's directory rather than being extracted
+/// into `profile/` directly.
+const CLIENT_OVERRIDES_PREFIX: &str = "client-overrides/";
+const CLIENT_OVERRIDES_DIR: &str = "client-overrides";
+
+/// Top-level, self-describing manifest for a `.foxpack` bundle, modeled
+/// on modpack distribution: enough to recreate the `BrowserProfile` shell
+/// via `ProfileManager`, with the actual profile data carried alongside
+/// under `overrides/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FoxpackManifest {
+  format_version: u32,
+  name: String,
+  browser: String,
+  version: String,
+  release_type: String,
+  camoufox_config: Option<CamoufoxConfig>,
+  wayfern_config: Option<WayfernConfig>,
+  proxy_settings: Option<ProxySettings>,
+  group_id: Option<String>,
+  /// Present on every bundle exported by this code: proof of which
+  /// install produced it. Absent only for `.foxpack` files written before
+  /// this field existed.
+  signature: Option<SignatureBlock>,
+  /// Present only when the exporter opted into encrypting `overrides/`
+  /// with a shared passphrase.
+  encryption: Option<EncryptionInfo>,
+}
+
+impl FoxpackManifest {
+  /// The bytes `signature` itself is computed over: the manifest with
+  /// `signature` cleared, serialized the same way on both the signing and
+  /// verifying side so the two never disagree about what was signed.
+  /// Covers manifest metadata only, not the `overrides/`/`client-overrides/`
+  /// archive payload, so a verified signature is proof of who exported the
+  /// bundle, not an integrity guarantee over the exact files it unpacks to.
+  fn signable_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+    let mut unsigned = self.clone();
+    unsigned.signature = None;
+    serde_json::to_vec(&unsigned)
+  }
+}
+
+/// Recorded in the manifest when `overrides/` was encrypted at export
+/// time. `salt` is random per export; the recipient derives the same
+/// AES-256-GCM key by running the passphrase they were given out of band
+/// through Argon2id with this salt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptionInfo {
+  salt: String,
+}
+
+/// How strictly `import_profile_pack` treats a bundle whose signature is
+/// missing or doesn't verify. Passed explicitly by the caller (rather
+/// than read from hidden global state) so the same install can, say,
+/// warn for bundles dragged in from a teammate's share drive but reject
+/// anything pulled from an untrusted public link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignaturePolicy {
+  /// Import regardless of whether the signature verifies.
+  Allow,
+  /// Import, but `signature_verified: false` tells the caller to warn.
+  Warn,
+  /// Refuse to import an unsigned or invalidly-signed bundle.
+  Reject,
+}
+
+impl Default for SignaturePolicy {
+  fn default() -> Self {
+    SignaturePolicy::Warn
+  }
+}
+
+/// Who signed an imported bundle, surfaced alongside the recreated
+/// profile so the caller can show the user whether this is a signer
+/// they've seen before.
+#[derive(Debug, Clone, Serialize)]
+pub struct SignerIdentity {
+  pub key_id: String,
+  pub known: bool,
+}
+
+/// Result of `import_profile_pack`: the recreated profile plus enough
+/// about the bundle's signature for the caller to decide how much to
+/// trust it.
+#[derive(Debug, Serialize)]
+pub struct ImportedProfilePack {
+  pub profile: BrowserProfile,
+  pub signer: Option<SignerIdentity>,
+  pub signature_verified: bool,
+}
+
+/// Derive an AES-256-GCM key from a bundle passphrase and its manifest
+/// salt. Not `crate::vault`'s `vault_key`: that key is per-install and
+/// kept in the OS keyring, while this one is a shared secret the
+/// exporter and importer both know, so it has to be derived fresh from
+/// whatever passphrase they agreed on out of band.
+fn derive_bundle_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+  let mut key = [0u8; 32];
+  argon2::Argon2::default()
+    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+    .expect("Argon2id key derivation must not fail for a fixed-size output");
+  key
+}
+
+fn encrypt_bytes(data: &[u8], key: &[u8; 32]) -> Vec<u8> {
+  let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+  let mut nonce_bytes = [0u8; 12];
+  AesOsRng.fill_bytes(&mut nonce_bytes);
+  let nonce = Nonce::from_slice(&nonce_bytes);
+
+  let ciphertext = cipher
+    .encrypt(nonce, data)
+    .expect("AES-256-GCM encryption must not fail for well-formed input");
+
+  let mut payload = Vec::with_capacity(12 + ciphertext.len());
+  payload.extend_from_slice(&nonce_bytes);
+  payload.extend_from_slice(&ciphertext);
+  payload
+}
+
+fn decrypt_bytes(payload: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+  if payload.len() < 12 {
+    return Err("encrypted bundle contents too short to contain a nonce".into());
+  }
+  let (nonce_bytes, ciphertext) = payload.split_at(12);
+  let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+  cipher
+    .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+    .map_err(|e| format!("failed to decrypt bundle contents (wrong passphrase?): {}", e).into())
+}
+
+/// Reconstruct the upstream proxy a profile is using from its `user.js`
+/// prefs. Credentials aren't recoverable this way (an authenticated
+/// proxy's password lives only inside the generated WebExtension, not as
+/// a plaintext pref), so a re-imported profile with an authenticated
+/// proxy will need its credentials re-entered.
+fn read_proxy_settings(profile_data_dir: &Path) -> Option<ProxySettings> {
+  let prefs = PrefStore::load(&profile_data_dir.join("user.js")).ok()?;
+  match prefs.get("network.proxy.type") {
+    Some(PrefValue::Int(1)) => {}
+    _ => return None,
+  }
+
+  if let Some(PrefValue::Str(host)) = prefs.get("network.proxy.socks") {
+    let port = match prefs.get("network.proxy.socks_port") {
+      Some(PrefValue::Int(p)) => *p as u16,
+      _ => return None,
+    };
+    let proxy_type = match prefs.get("network.proxy.socks_version") {
+      Some(PrefValue::Int(4)) => "socks4",
+      _ => "socks5",
+    };
+    return Some(ProxySettings {
+      proxy_type: proxy_type.to_string(),
+      host: host.clone(),
+      port,
+      username: None,
+      password: None,
+    });
+  }
+
+  if let Some(PrefValue::Str(host)) = prefs.get("network.proxy.http") {
+    let port = match prefs.get("network.proxy.http_port") {
+      Some(PrefValue::Int(p)) => *p as u16,
+      _ => return None,
+    };
+    return Some(ProxySettings {
+      proxy_type: "http".to_string(),
+      host: host.clone(),
+      port,
+      username: None,
+      password: None,
+    });
+  }
+
+  None
+}
+
+impl ProfileManager {
+  /// Package a profile into a self-describing `.foxpack` zip: a
+  /// `manifest.json` with everything needed to recreate the profile
+  /// shell, plus `overrides/` holding its data directory verbatim.
+  /// `passphrase`, if given, encrypts the `overrides/` payload (cookies,
+  /// sessions, anything else copied verbatim into the profile's data
+  /// dir) under a key derived from it, since that's the part of the
+  /// bundle that can carry live credentials.
+  pub fn export_profile_pack(
+    &self,
+    profile_id: &str,
+    dest: &Path,
+    passphrase: Option<&str>,
+  ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+
+    let profile_data_dir = profile.get_profile_data_path(&self.get_profiles_dir());
+
+    let encryption = if passphrase.is_some() {
+      let mut salt = [0u8; 16];
+      rand::rngs::OsRng.fill_bytes(&mut salt);
+      Some(EncryptionInfo { salt: STANDARD.encode(salt) })
+    } else {
+      None
+    };
+
+    let mut manifest = FoxpackManifest {
+      format_version: FOXPACK_FORMAT_VERSION,
+      name: profile.name.clone(),
+      browser: profile.browser.clone(),
+      version: profile.version.clone(),
+      release_type: profile.release_type.clone(),
+      camoufox_config: profile.camoufox_config.clone(),
+      wayfern_config: profile.wayfern_config.clone(),
+      proxy_settings: read_proxy_settings(&profile_data_dir),
+      group_id: profile.group_id.clone(),
+      signature: None,
+      encryption,
+    };
+    manifest.signature = Some(signing::sign(&manifest.signable_bytes()?)?);
+
+    let encrypt_key = match (passphrase, &manifest.encryption) {
+      (Some(passphrase), Some(info)) => Some(derive_bundle_key(passphrase, &STANDARD.decode(&info.salt)?)),
+      _ => None,
+    };
+
+    let file = fs::File::create(dest)?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    add_dir_to_zip(
+      &mut zip,
+      &profile_data_dir,
+      OVERRIDES_PREFIX.trim_end_matches('/'),
+      options,
+      encrypt_key.as_ref(),
+    )?;
+
+    zip.finish()?;
+    Ok(dest.to_path_buf())
+  }
+
+  /// Create a new profile from a `.foxpack` bundle's manifest, then walk
+  /// the archive extracting `overrides/` entries straight into the new
+  /// profile's data directory and `client-overrides/` entries into a
+  /// sibling directory for a future launch step to merge in. Directory
+  /// entries are skipped; prefix matching is exact so e.g.
+  /// `overrides-extra/` (not a real prefix this format uses) isn't
+  /// silently swallowed by a loose `starts_with("overrides")` check.
+  /// `passphrase` is required when the bundle's `overrides/` payload was
+  /// encrypted at export time, and ignored otherwise. `policy` decides
+  /// what happens when the bundle's signature is missing or doesn't
+  /// verify: `Reject` aborts the import outright, while `Allow`/`Warn`
+  /// both proceed and report `signature_verified` for the caller to act
+  /// on.
+  pub async fn import_profile_pack(
+    &self,
+    app_handle: &tauri::AppHandle,
+    src: &Path,
+    passphrase: Option<&str>,
+    policy: SignaturePolicy,
+  ) -> Result<ImportedProfilePack, Box<dyn std::error::Error>> {
+    let file = fs::File::open(src)?;
+    let mut archive = ZipArchive::new(file)?;
+
+    let manifest: FoxpackManifest = {
+      let mut entry = archive.by_name("manifest.json")?;
+      let mut contents = String::new();
+      entry.read_to_string(&mut contents)?;
+      serde_json::from_str(&contents)?
+    };
+
+    if manifest.format_version > FOXPACK_FORMAT_VERSION {
+      return Err(format!(".foxpack format version {} is newer than supported", manifest.format_version).into());
+    }
+
+    let signature_verified = match &manifest.signature {
+      Some(block) => signing::verify(&manifest.signable_bytes()?, block).is_ok(),
+      None => false,
+    };
+    if !signature_verified && policy == SignaturePolicy::Reject {
+      return Err("bundle signature is missing or invalid; refusing to import under the current signature policy".into());
+    }
+
+    let previously_known = match &manifest.signature {
+      Some(block) => self
+        .list_known_signers()?
+        .iter()
+        .any(|signer| signer.key_id == block.key_id),
+      None => false,
+    };
+
+    let decrypt_key = match (&manifest.encryption, passphrase) {
+      (Some(info), Some(passphrase)) => Some(derive_bundle_key(passphrase, &STANDARD.decode(&info.salt)?)),
+      (Some(_), None) => return Err("bundle overrides are encrypted; a passphrase is required to import it".into()),
+      (None, _) => None,
+    };
+
+    let profile = crate::profile::manager::ProfileBuilder::new(manifest.name, manifest.browser, manifest.version)
+      .release_type(manifest.release_type)
+      .camoufox_config(manifest.camoufox_config)
+      .wayfern_config(manifest.wayfern_config)
+      .group_id(manifest.group_id)
+      .proxy_settings(manifest.proxy_settings)
+      .build(app_handle)
+      .await?;
+
+    let profile_uuid_dir = self.get_profiles_dir().join(profile.id.to_string());
+    let profile_data_dir = profile_uuid_dir.join("profile");
+    let client_overrides_dir = profile_uuid_dir.join(CLIENT_OVERRIDES_DIR);
+
+    for i in 0..archive.len() {
+      let mut entry = archive.by_index(i)?;
+      let name = entry.name().to_string();
+
+      let (rel_path, out_dir, is_override) = if let Some(rel) = name.strip_prefix(OVERRIDES_PREFIX) {
+        (rel, &profile_data_dir, true)
+      } else if let Some(rel) = name.strip_prefix(CLIENT_OVERRIDES_PREFIX) {
+        (rel, &client_overrides_dir, false)
+      } else {
+        continue;
+      };
+
+      if rel_path.is_empty() || entry.is_dir() {
+        continue;
+      }
+      if !is_safe_rel_path(Path::new(rel_path)) {
+        log::warn!("Skipping zip entry with unsafe path traversal: {}", rel_path);
+        continue;
+      }
+
+      let mut bytes = Vec::new();
+      entry.read_to_end(&mut bytes)?;
+      if is_override {
+        if let Some(key) = &decrypt_key {
+          bytes = decrypt_bytes(&bytes, key)?;
+        }
+      }
+
+      let Some(out_path) = confine_to_dir(out_dir, rel_path)? else {
+        log::warn!("Skipping zip entry that escapes its extraction directory: {}", rel_path);
+        continue;
+      };
+      fs::write(&out_path, &bytes)?;
+    }
+
+    let signer = match &manifest.signature {
+      Some(block) => {
+        if signature_verified {
+          self.remember_signer(&block.key_id, &block.public_key)?;
+        }
+        Some(SignerIdentity {
+          key_id: block.key_id.clone(),
+          known: previously_known,
+        })
+      }
+      None => None,
+    };
+
+    let _ = crate::events::emit_empty("profiles-changed");
+    Ok(ImportedProfilePack {
+      profile,
+      signer,
+      signature_verified,
+    })
+  }
+}
+
+/// Whether a zip entry's path (with its known archive prefix already
+/// stripped) looks safe to join onto an extraction directory: no `..`
+/// traversal and no absolute-path component (`Path::join` silently
+/// discards its base when the right-hand side is absolute, e.g.
+/// `out_dir.join("/etc/cron.d/evil")` yields `/etc/cron.d/evil`). This is
+/// a first-pass filter only - `confine_to_dir` does the actual
+/// containment check against the resolved filesystem path.
+fn is_safe_rel_path(rel_path: &Path) -> bool {
+  use std::path::Component;
+  rel_path
+    .components()
+    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Join `rel_path` onto `out_dir` and verify the result actually resolves
+/// inside `out_dir`, rather than trusting `is_safe_rel_path`'s
+/// component-level check alone (e.g. a symlinked intermediate directory
+/// could still escape it). Creates `out_dir` and any missing parent
+/// directories for `rel_path` as a side effect, since `canonicalize`
+/// requires the path to exist. Returns `None` if the resolved path
+/// escapes `out_dir`.
+fn confine_to_dir(out_dir: &Path, rel_path: &str) -> std::io::Result<Option<PathBuf>> {
+  let out_path = out_dir.join(rel_path);
+  let parent = out_path.parent().unwrap_or(out_dir);
+  fs::create_dir_all(parent)?;
+
+  let canonical_out_dir = fs::canonicalize(out_dir)?;
+  let canonical_parent = fs::canonicalize(parent)?;
+  if !canonical_parent.starts_with(&canonical_out_dir) {
+    return Ok(None);
+  }
+
+  Ok(Some(out_path))
+}
+
+/// Recursively add every file under `dir` to `zip`, rooted at
+/// `zip_prefix`. When `encrypt_key` is set, each file's bytes are
+/// AES-256-GCM-encrypted (with its own random nonce) before being
+/// written, rather than the whole archive being encrypted at once, so
+/// the manifest stays readable without a passphrase.
+fn add_dir_to_zip(
+  zip: &mut ZipWriter<fs::File>,
+  dir: &Path,
+  zip_prefix: &str,
+  options: FileOptions,
+  encrypt_key: Option<&[u8; 32]>,
+) -> Result<(), Box<dyn std::error::Error>> {
+  if !dir.exists() {
+    return Ok(());
+  }
+  for entry in fs::read_dir(dir)? {
+    let entry = entry?;
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().into_owned();
+    let zip_path = format!("{}/{}", zip_prefix, name);
+    if path.is_dir() {
+      add_dir_to_zip(zip, &path, &zip_path, options, encrypt_key)?;
+    } else {
+      zip.start_file(&zip_path, options)?;
+      let bytes = fs::read(&path)?;
+      match encrypt_key {
+        Some(key) => zip.write_all(&encrypt_bytes(&bytes, key))?,
+        None => zip.write_all(&bytes)?,
+      }
+    }
+  }
+  Ok(())
+}
+
+#[tauri::command]
+pub fn export_profile_pack(profile_id: String, dest: String, passphrase: Option<String>) -> Result<String, String> {
+  ProfileManager::instance()
+    .export_profile_pack(&profile_id, Path::new(&dest), passphrase.as_deref())
+    .map(|path| path.to_string_lossy().into_owned())
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_profile_pack(
+  app_handle: tauri::AppHandle,
+  src: String,
+  passphrase: Option<String>,
+  policy: SignaturePolicy,
+) -> Result<ImportedProfilePack, String> {
+  ProfileManager::instance()
+    .import_profile_pack(&app_handle, Path::new(&src), passphrase.as_deref(), policy)
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,184 @@
+use crate::browser::ProxySettings;
+use crate::camoufox_manager::CamoufoxConfig;
+use crate::profile::manager::ProfileManager;
+use crate::profile::types::BrowserProfile;
+use crate::runner::Runner;
+use crate::wayfern_manager::WayfernConfig;
+use std::io;
+use std::process::ExitStatus;
+
+/// Chained-setter builder that assembles everything needed to launch a
+/// profile's browser — proxy, fingerprint config, extra CLI args, and
+/// environment overrides — before a single `start()` actually spawns it.
+/// Named and shaped after `crate::runner::BrowserRunner`/`std::process::Command`,
+/// one layer up: where that one turns a resolved binary path and argument
+/// list into a process, this one turns a `BrowserProfile` plus configuration
+/// into that binary path and argument list. Separating assembly from
+/// spawning is what lets `update_camoufox_config`/`update_wayfern_config`
+/// validate a configuration by building a runner without ever starting it.
+pub struct BrowserRunner {
+  profile: BrowserProfile,
+  proxy: Option<ProxySettings>,
+  camoufox_config: Option<CamoufoxConfig>,
+  wayfern_config: Option<WayfernConfig>,
+  extra_args: Vec<String>,
+  envs: Vec<(String, String)>,
+}
+
+impl BrowserRunner {
+  pub fn new(profile: BrowserProfile) -> Self {
+    let camoufox_config = profile.camoufox_config.clone();
+    let wayfern_config = profile.wayfern_config.clone();
+    Self {
+      profile,
+      proxy: None,
+      camoufox_config,
+      wayfern_config,
+      extra_args: Vec::new(),
+      envs: Vec::new(),
+    }
+  }
+
+  pub fn proxy(mut self, proxy: Option<ProxySettings>) -> Self {
+    self.proxy = proxy;
+    self
+  }
+
+  pub fn camoufox(mut self, config: CamoufoxConfig) -> Self {
+    self.camoufox_config = Some(config);
+    self
+  }
+
+  pub fn wayfern(mut self, config: WayfernConfig) -> Self {
+    self.wayfern_config = Some(config);
+    self
+  }
+
+  pub fn extra_args(mut self, args: Vec<String>) -> Self {
+    self.extra_args = args;
+    self
+  }
+
+  pub fn env(mut self, key: impl Into<String>, val: impl Into<String>) -> Self {
+    self.envs.push((key.into(), val.into()));
+    self
+  }
+
+  /// Resolve the binary this configuration would launch, without
+  /// spawning anything. Used both by `start()` and by callers (e.g.
+  /// config validation) that only want to check the configuration
+  /// resolves to a real, existing executable.
+  fn resolve_executable_path(&self) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let manager = ProfileManager::instance();
+    let mut browser_dir = manager.get_binaries_dir();
+    browser_dir.push(&self.profile.browser);
+    browser_dir.push(&self.profile.version);
+
+    let configured = match self.profile.browser.as_str() {
+      "camoufox" => self.camoufox_config.as_ref().and_then(|c| c.executable_path.clone()),
+      "wayfern" => self.wayfern_config.as_ref().and_then(|c| c.executable_path.clone()),
+      _ => None,
+    };
+
+    let path = if let Some(configured) = configured {
+      std::path::PathBuf::from(configured)
+    } else {
+      match self.profile.browser.as_str() {
+        "camoufox" => {
+          #[cfg(target_os = "macos")]
+          let p = browser_dir.join("Camoufox.app/Contents/MacOS/camoufox");
+          #[cfg(target_os = "windows")]
+          let p = browser_dir.join("camoufox.exe");
+          #[cfg(target_os = "linux")]
+          let p = browser_dir.join("camoufox");
+          p
+        }
+        "wayfern" => {
+          #[cfg(target_os = "macos")]
+          let p = browser_dir.join("Chromium.app/Contents/MacOS/Chromium");
+          #[cfg(target_os = "windows")]
+          let p = browser_dir.join("chrome.exe");
+          #[cfg(target_os = "linux")]
+          let p = browser_dir.join("chrome");
+          p
+        }
+        other => return Err(format!("Unknown browser type '{}'", other).into()),
+      }
+    };
+
+    if !path.exists() {
+      return Err(format!("Browser executable not found at {}", path.display()).into());
+    }
+    Ok(path)
+  }
+
+  /// Check that this configuration resolves to a real executable without
+  /// launching it — what `update_camoufox_config`/`update_wayfern_config`
+  /// use to reject a bad config before it's ever saved.
+  pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+    self.resolve_executable_path().map(|_| ())
+  }
+
+  /// Apply the accumulated proxy settings to the profile's `user.js`
+  /// (or clear them if none were set), resolve the executable, and spawn
+  /// it bound to the profile's data directory.
+  pub fn start(&self) -> Result<BrowserProcess, Box<dyn std::error::Error>> {
+    let manager = ProfileManager::instance();
+    let profiles_dir = manager.get_profiles_dir();
+    let profile_data_dir = self.profile.get_profile_data_path(&profiles_dir);
+
+    match &self.proxy {
+      Some(proxy) => manager.apply_proxy_settings_to_profile(&profile_data_dir, proxy, None)?,
+      None => manager.disable_proxy_settings_in_profile(&profile_data_dir)?,
+    }
+
+    if let Some(camoufox_config) = &self.camoufox_config {
+      crate::camoufox_manager::CamoufoxManager::instance().apply_custom_prefs(&profile_data_dir, camoufox_config)?;
+    }
+
+    let executable_path = self.resolve_executable_path()?;
+    let mut runner = crate::runner::BrowserRunner::new(&executable_path, &profile_data_dir);
+    runner.args(&self.extra_args);
+    crate::env_sanitize::apply_to(&mut runner);
+    for (key, value) in &self.envs {
+      runner.env(key, value);
+    }
+
+    let process = runner.start()?;
+    Ok(BrowserProcess {
+      process,
+      profile_id: self.profile.id,
+    })
+  }
+}
+
+/// A spawned profile browser process. Thin wrapper over
+/// `crate::runner::RunnerProcess` that also remembers which profile it
+/// belongs to, so a caller juggling several launches doesn't have to
+/// track that mapping itself.
+pub struct BrowserProcess {
+  process: crate::runner::RunnerProcess,
+  profile_id: uuid::Uuid,
+}
+
+impl BrowserProcess {
+  pub fn profile_id(&self) -> uuid::Uuid {
+    self.profile_id
+  }
+
+  pub fn id(&self) -> u32 {
+    self.process.id()
+  }
+
+  pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+    self.process.try_status()
+  }
+
+  pub fn wait(&mut self) -> io::Result<ExitStatus> {
+    self.process.wait()
+  }
+
+  pub fn kill(&mut self) -> io::Result<()> {
+    self.process.kill()
+  }
+}
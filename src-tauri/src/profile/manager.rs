@@ -5,6 +5,7 @@ use crate::profile::types::BrowserProfile;
 use crate::wayfern_manager::WayfernConfig;
 use directories::BaseDirs;
 use std::fs::{self, create_dir_all};
+use std::io;
 use std::path::{Path, PathBuf};
 
 pub struct ProfileManager {
@@ -13,68 +14,173 @@ pub struct ProfileManager {
   wayfern_manager: &'static crate::wayfern_manager::WayfernManager,
 }
 
-impl ProfileManager {
-  fn new() -> Self {
+/// Discriminates `ProxySettings::proxy_type` so proxy prefs are written
+/// for the one protocol actually in use, instead of stamping the same
+/// host/port across http/ssl/socks at once. Defaults to `Http` for an
+/// unrecognized or missing value, matching the old behavior's implicit
+/// HTTP assumption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProxyProtocol {
+  Http,
+  Https,
+  Socks4,
+  Socks5,
+}
+
+impl ProxyProtocol {
+  fn parse(proxy_type: &str) -> Self {
+    match proxy_type.to_lowercase().as_str() {
+      "https" => ProxyProtocol::Https,
+      "socks4" | "socks4a" => ProxyProtocol::Socks4,
+      "socks5" | "socks" => ProxyProtocol::Socks5,
+      _ => ProxyProtocol::Http,
+    }
+  }
+}
+
+/// Chained-setter builder for `BrowserProfile` creation, replacing the
+/// long positional argument list `create_profile_with_group` used to take.
+/// Mirrors the move mozrunner's profile builder made away from a
+/// Python-style constructor call: every optional knob gets its own
+/// setter, and `build` is the single place that knows how to resolve an
+/// executable path, generate a fingerprint, and extract a user agent —
+/// instead of that logic being duplicated per caller.
+pub struct ProfileBuilder {
+  manager: &'static ProfileManager,
+  name: String,
+  browser: String,
+  version: String,
+  release_type: String,
+  proxy_id: Option<String>,
+  camoufox_config: Option<CamoufoxConfig>,
+  wayfern_config: Option<WayfernConfig>,
+  group_id: Option<String>,
+  username: Option<String>,
+  password: Option<String>,
+  proxy_settings: Option<ProxySettings>,
+  tags: Vec<String>,
+  note: Option<String>,
+}
+
+impl ProfileBuilder {
+  pub fn new(name: impl Into<String>, browser: impl Into<String>, version: impl Into<String>) -> Self {
     Self {
-      base_dirs: BaseDirs::new().expect("Failed to get base directories"),
-      camoufox_manager: crate::camoufox_manager::CamoufoxManager::instance(),
-      wayfern_manager: crate::wayfern_manager::WayfernManager::instance(),
+      manager: ProfileManager::instance(),
+      name: name.into(),
+      browser: browser.into(),
+      version: version.into(),
+      release_type: crate::profile::types::default_release_type(),
+      proxy_id: None,
+      camoufox_config: None,
+      wayfern_config: None,
+      group_id: None,
+      username: None,
+      password: None,
+      proxy_settings: None,
+      tags: Vec::new(),
+      note: None,
     }
   }
 
-  pub fn instance() -> &'static ProfileManager {
-    &PROFILE_MANAGER
+  pub fn release_type(mut self, release_type: impl Into<String>) -> Self {
+    self.release_type = release_type.into();
+    self
   }
 
-  pub fn get_profiles_dir(&self) -> PathBuf {
-    let mut path = self.base_dirs.data_local_dir().to_path_buf();
-    path.push(if cfg!(debug_assertions) {
-      "FoxiaDev"
-    } else {
-      "Foxia"
-    });
-    path.push("profiles");
-    path
+  pub fn proxy_id(mut self, proxy_id: Option<String>) -> Self {
+    self.proxy_id = proxy_id;
+    self
   }
 
-  pub fn get_binaries_dir(&self) -> PathBuf {
-    let mut path = self.base_dirs.data_local_dir().to_path_buf();
-    path.push(if cfg!(debug_assertions) {
-      "FoxiaDev"
-    } else {
-      "Foxia"
-    });
-    path.push("binaries");
-    path
+  pub fn camoufox_config(mut self, config: Option<CamoufoxConfig>) -> Self {
+    self.camoufox_config = config;
+    self
   }
 
-  #[allow(clippy::too_many_arguments)]
-  pub async fn create_profile_with_group(
-    &self,
-    app_handle: &tauri::AppHandle,
-    name: &str,
-    browser: &str,
-    version: &str,
-    release_type: &str,
-    proxy_id: Option<String>,
-    camoufox_config: Option<CamoufoxConfig>,
-    wayfern_config: Option<WayfernConfig>,
-    group_id: Option<String>,
-    username: Option<String>,
-    password: Option<String>,
-  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
-    log::info!("Attempting to create profile: {name}");
+  pub fn wayfern_config(mut self, config: Option<WayfernConfig>) -> Self {
+    self.wayfern_config = config;
+    self
+  }
 
-    let existing_profiles = self.list_profiles()?;
+  pub fn group_id(mut self, group_id: Option<String>) -> Self {
+    self.group_id = group_id;
+    self
+  }
+
+  pub fn credentials(mut self, username: Option<String>, password: Option<String>) -> Self {
+    self.username = username;
+    self.password = password;
+    self
+  }
+
+  /// Direct upstream proxy to apply to `user.js` on creation (as opposed
+  /// to `proxy_id`, which only references the local forwarding process).
+  pub fn proxy_settings(mut self, proxy_settings: Option<ProxySettings>) -> Self {
+    self.proxy_settings = proxy_settings;
+    self
+  }
+
+  pub fn tags(mut self, tags: Vec<String>) -> Self {
+    self.tags = tags;
+    self
+  }
+
+  pub fn note(mut self, note: Option<String>) -> Self {
+    self.note = note;
+    self
+  }
+
+  /// A throwaway `BrowserProfile` carrying just enough identity/config to
+  /// pass to `generate_fingerprint_config`, which needs a `BrowserProfile`
+  /// even though the real one doesn't exist on disk yet.
+  fn temp_profile(&self) -> BrowserProfile {
+    BrowserProfile {
+      id: uuid::Uuid::new_v4(),
+      name: self.name.clone(),
+      browser: self.browser.clone(),
+      version: self.version.clone(),
+      proxy_id: self.proxy_id.clone(),
+      process_id: None,
+      last_launch: None,
+      release_type: self.release_type.clone(),
+      camoufox_config: None,
+      wayfern_config: None,
+      group_id: self.group_id.clone(),
+      tags: Vec::new(),
+      note: None,
+      sync_enabled: false,
+      last_sync: None,
+      odoo_id: None,
+      profile_url: None,
+      created_at: Some(chrono::Utc::now().timestamp() as u64),
+      odoo_proxy: None,
+      username: self.username.clone().map(crate::vault::SealedSecretString::new),
+      password: self.password.clone().map(crate::vault::SealedSecretString::new),
+      user_agent: None,
+      webdriver_port: None,
+      sync_macaroon: None,
+      automation: None,
+      attached_port: None,
+      absolute_path: None,
+    }
+  }
+
+  /// Run the dedup check, resolve the executable path, generate a
+  /// fingerprint if none was supplied, extract the user agent, persist
+  /// the profile, and apply (or clear) its proxy settings.
+  pub async fn build(&self, app_handle: &tauri::AppHandle) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    log::info!("Attempting to create profile: {}", self.name);
+
+    let existing_profiles = self.manager.list_profiles()?;
     if existing_profiles
       .iter()
-      .any(|p| p.name.to_lowercase() == name.to_lowercase())
+      .any(|p| p.name.to_lowercase() == self.name.to_lowercase())
     {
-      return Err(format!("Profile with name '{name}' already exists").into());
+      return Err(format!("Profile with name '{}' already exists", self.name).into());
     }
 
     let profile_id = uuid::Uuid::new_v4();
-    let profiles_dir = self.get_profiles_dir();
+    let profiles_dir = self.manager.get_profiles_dir();
     let profile_uuid_dir = profiles_dir.join(profile_id.to_string());
     let profile_data_dir = profile_uuid_dir.join("profile");
     create_dir_all(&profile_uuid_dir)?;
@@ -82,12 +188,12 @@ impl ProfileManager {
 
     let mut user_agent = None;
 
-    let final_camoufox_config = if browser == "camoufox" {
-      let mut config = camoufox_config.unwrap_or_default();
+    let final_camoufox_config = if self.browser == "camoufox" {
+      let mut config = self.camoufox_config.clone().unwrap_or_default();
       if config.executable_path.is_none() {
-        let mut browser_dir = self.get_binaries_dir();
-        browser_dir.push(browser);
-        browser_dir.push(version);
+        let mut browser_dir = self.manager.get_binaries_dir();
+        browser_dir.push(&self.browser);
+        browser_dir.push(&self.version);
         #[cfg(target_os = "macos")]
         let binary_path = browser_dir.join("Camoufox.app/Contents/MacOS/camoufox");
         #[cfg(target_os = "windows")]
@@ -98,32 +204,9 @@ impl ProfileManager {
       }
 
       if config.fingerprint.is_none() {
-        let temp_profile = BrowserProfile {
-          id: uuid::Uuid::new_v4(),
-          name: name.to_string(),
-          browser: browser.to_string(),
-          version: version.to_string(),
-          proxy_id: proxy_id.clone(),
-          process_id: None,
-          last_launch: None,
-          release_type: release_type.to_string(),
-          camoufox_config: None,
-          wayfern_config: None,
-          group_id: group_id.clone(),
-          tags: Vec::new(),
-          note: None,
-          sync_enabled: false,
-          last_sync: None,
-          odoo_id: None,
-          profile_url: None,
-          created_at: Some(chrono::Utc::now().timestamp() as u64),
-          odoo_proxy: None,
-          username: username.clone(),
-          password: password.clone(),
-          user_agent: None,
-          absolute_path: None,
-        };
+        let temp_profile = self.temp_profile();
         if let Ok(gen_fp) = self
+          .manager
           .camoufox_manager
           .generate_fingerprint_config(app_handle, &temp_profile, &config)
           .await
@@ -149,15 +232,15 @@ impl ProfileManager {
       config.proxy = None;
       Some(config)
     } else {
-      camoufox_config
+      self.camoufox_config.clone()
     };
 
-    let final_wayfern_config = if browser == "wayfern" {
-      let mut config = wayfern_config.unwrap_or_default();
+    let final_wayfern_config = if self.browser == "wayfern" {
+      let mut config = self.wayfern_config.clone().unwrap_or_default();
       if config.executable_path.is_none() {
-        let mut browser_dir = self.get_binaries_dir();
-        browser_dir.push(browser);
-        browser_dir.push(version);
+        let mut browser_dir = self.manager.get_binaries_dir();
+        browser_dir.push(&self.browser);
+        browser_dir.push(&self.version);
         #[cfg(target_os = "macos")]
         let binary_path = browser_dir.join("Chromium.app/Contents/MacOS/Chromium");
         #[cfg(target_os = "windows")]
@@ -168,32 +251,9 @@ impl ProfileManager {
       }
 
       if config.fingerprint.is_none() {
-        let temp_profile = BrowserProfile {
-          id: uuid::Uuid::new_v4(),
-          name: name.to_string(),
-          browser: browser.to_string(),
-          version: version.to_string(),
-          proxy_id: proxy_id.clone(),
-          process_id: None,
-          last_launch: None,
-          release_type: release_type.to_string(),
-          camoufox_config: None,
-          wayfern_config: None,
-          group_id: group_id.clone(),
-          tags: Vec::new(),
-          note: None,
-          sync_enabled: false,
-          last_sync: None,
-          odoo_id: None,
-          profile_url: None,
-          created_at: Some(chrono::Utc::now().timestamp() as u64),
-          odoo_proxy: None,
-          username: username.clone(),
-          password: password.clone(),
-          user_agent: None,
-          absolute_path: None,
-        };
+        let temp_profile = self.temp_profile();
         if let Ok(gen_fp) = self
+          .manager
           .wayfern_manager
           .generate_fingerprint_config(app_handle, &temp_profile, &config)
           .await
@@ -217,40 +277,130 @@ impl ProfileManager {
       config.proxy = None;
       Some(config)
     } else {
-      wayfern_config
+      self.wayfern_config.clone()
     };
 
     let profile = BrowserProfile {
       id: profile_id,
-      name: name.to_string(),
-      browser: browser.to_string(),
-      version: version.to_string(),
-      proxy_id: proxy_id.clone(),
+      name: self.name.clone(),
+      browser: self.browser.clone(),
+      version: self.version.clone(),
+      proxy_id: self.proxy_id.clone(),
       process_id: None,
       last_launch: None,
-      release_type: release_type.to_string(),
+      release_type: self.release_type.clone(),
       camoufox_config: final_camoufox_config,
       wayfern_config: final_wayfern_config,
-      group_id: group_id.clone(),
-      tags: Vec::new(),
-      note: None,
+      group_id: self.group_id.clone(),
+      tags: self.tags.clone(),
+      note: self.note.clone(),
       sync_enabled: false,
       last_sync: None,
       odoo_id: None,
       profile_url: None,
       created_at: Some(chrono::Utc::now().timestamp() as u64),
       odoo_proxy: None,
-      username,
-      password,
+      username: self.username.clone().map(crate::vault::SealedSecretString::new),
+      password: self.password.clone().map(crate::vault::SealedSecretString::new),
       user_agent,
+      webdriver_port: None,
+      sync_macaroon: None,
+      automation: None,
+      attached_port: None,
       absolute_path: None,
     };
 
-    self.save_profile(&profile)?;
-    self.disable_proxy_settings_in_profile(&profile_data_dir)?;
+    self.manager.save_profile(&profile)?;
+    match &self.proxy_settings {
+      Some(settings) => self
+        .manager
+        .apply_proxy_settings_to_profile(&profile_data_dir, settings, None)?,
+      None => self.manager.disable_proxy_settings_in_profile(&profile_data_dir)?,
+    }
     let _ = events::emit_empty("profiles-changed");
     Ok(profile)
   }
+}
+
+impl ProfileManager {
+  fn new() -> Self {
+    Self {
+      base_dirs: BaseDirs::new().expect("Failed to get base directories"),
+      camoufox_manager: crate::camoufox_manager::CamoufoxManager::instance(),
+      wayfern_manager: crate::wayfern_manager::WayfernManager::instance(),
+    }
+  }
+
+  pub fn instance() -> &'static ProfileManager {
+    &PROFILE_MANAGER
+  }
+
+  pub fn get_profiles_dir(&self) -> PathBuf {
+    let mut path = self.base_dirs.data_local_dir().to_path_buf();
+    path.push(if cfg!(debug_assertions) {
+      "FoxiaDev"
+    } else {
+      "Foxia"
+    });
+    path.push("profiles");
+    path
+  }
+
+  pub fn get_binaries_dir(&self) -> PathBuf {
+    let mut path = self.base_dirs.data_local_dir().to_path_buf();
+    path.push(if cfg!(debug_assertions) {
+      "FoxiaDev"
+    } else {
+      "Foxia"
+    });
+    path.push("binaries");
+    path
+  }
+
+  /// Where this install's signing keypair metadata and known-signers
+  /// registry live. The signing key itself is in the OS keyring (see
+  /// `crate::profile::signing`); this directory only holds the
+  /// known-signers list.
+  pub fn get_signing_dir(&self) -> PathBuf {
+    let mut path = self.base_dirs.data_local_dir().to_path_buf();
+    path.push(if cfg!(debug_assertions) {
+      "FoxiaDev"
+    } else {
+      "Foxia"
+    });
+    path.push("signing");
+    path
+  }
+
+  /// Thin wrapper over `ProfileBuilder` kept for backward compatibility
+  /// with existing callers; new call sites should prefer the builder.
+  #[allow(clippy::too_many_arguments)]
+  pub async fn create_profile_with_group(
+    &self,
+    app_handle: &tauri::AppHandle,
+    name: &str,
+    browser: &str,
+    version: &str,
+    release_type: &str,
+    proxy_id: Option<String>,
+    camoufox_config: Option<CamoufoxConfig>,
+    wayfern_config: Option<WayfernConfig>,
+    group_id: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    proxy_settings: Option<ProxySettings>,
+  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    ProfileBuilder::new(name, browser, version)
+      .release_type(release_type)
+      .proxy_id(proxy_id)
+      .camoufox_config(camoufox_config)
+      .wayfern_config(wayfern_config)
+      .group_id(group_id)
+      .credentials(username, password)
+      .proxy_settings(proxy_settings)
+      .build(app_handle)
+      .await
+  }
 
   pub fn save_profile(&self, profile: &BrowserProfile) -> Result<(), Box<dyn std::error::Error>> {
     let profiles_dir = self.get_profiles_dir();
@@ -319,8 +469,8 @@ impl ProfileManager {
       .ok_or("Profile not found")?;
 
     profile.name = name;
-    profile.username = username;
-    profile.password = password;
+    profile.username = username.map(crate::vault::SealedSecretString::new);
+    profile.password = password.map(crate::vault::SealedSecretString::new);
     profile.user_agent = user_agent;
 
     self.save_profile(&profile)?;
@@ -356,6 +506,17 @@ impl ProfileManager {
       .into_iter()
       .find(|p| p.id == profile_uuid)
       .ok_or("Profile not found")?;
+
+    self
+      .camoufox_manager
+      .validate_custom_prefs(&config.custom_prefs)
+      .map_err(|e| e.to_string())?;
+
+    crate::profile::launch::BrowserRunner::new(profile.clone())
+      .camoufox(config.clone())
+      .validate()
+      .map_err(|e| e.to_string())?;
+
     profile.camoufox_config = Some(config);
     self.save_profile(&profile).map_err(|e| e.to_string())?;
     let _ = events::emit_empty("profiles-changed");
@@ -375,6 +536,12 @@ impl ProfileManager {
       .into_iter()
       .find(|p| p.id == profile_uuid)
       .ok_or("Profile not found")?;
+
+    crate::profile::launch::BrowserRunner::new(profile.clone())
+      .wayfern(config.clone())
+      .validate()
+      .map_err(|e| e.to_string())?;
+
     profile.wayfern_config = Some(config);
     self.save_profile(&profile).map_err(|e| e.to_string())?;
     let _ = events::emit_empty("profiles-changed");
@@ -472,11 +639,40 @@ impl ProfileManager {
     Ok(profile)
   }
 
+  /// Mint and store a fresh sync macaroon scoped to `profile_id`, expiring
+  /// `ttl_secs` from now. Returns the updated profile; the caller can read
+  /// `profile.sync_macaroon` to hand the token to a sync coordinator.
+  pub fn issue_profile_sync_macaroon(
+    &self,
+    _app_handle: &tauri::AppHandle,
+    profile_id: &str,
+    ttl_secs: u64,
+  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let mut profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+
+    let now = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)?
+      .as_secs();
+    profile.sync_macaroon = Some(crate::macaroon::issue_sync_macaroon(&profile_uuid, now, ttl_secs)?);
+
+    self.save_profile(&profile)?;
+    let _ = events::emit_empty("profiles-changed");
+    Ok(profile)
+  }
+
   pub async fn check_browser_status(
     &self,
     _app_handle: tauri::AppHandle,
     profile: &BrowserProfile,
   ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(port) = profile.attached_port {
+      return Ok(probe_tcp_port(port).await);
+    }
     if profile.browser == "camoufox" {
       let launcher = self.camoufox_manager;
       let profiles_dir = self.get_profiles_dir();
@@ -484,11 +680,118 @@ impl ProfileManager {
       let path_str = profile_data_path.to_string_lossy();
       match launcher.find_camoufox_by_profile(&path_str).await {
         Ok(Some(_)) => Ok(true),
-        _ => Ok(false),
+        _ => Ok(probe_automation_port(profile).await),
       }
+    } else if profile.process_id.is_some() {
+      Ok(true)
+    } else {
+      Ok(probe_automation_port(profile).await)
+    }
+  }
+
+  /// Turn remote automation on or off for a profile. Enabling picks a
+  /// free loopback port, writes `marionette.port` (plus
+  /// `devtools.debugger.remote-enabled` and `devtools.debugger.remote-port`)
+  /// into the profile's `user.js`, and records the resulting control
+  /// endpoint on the profile. Disabling clears both the prefs and the
+  /// stored endpoint. The browser must be relaunched for the change to
+  /// take effect.
+  pub fn enable_profile_automation(
+    &self,
+    _app_handle: &tauri::AppHandle,
+    profile_id: &str,
+    enabled: bool,
+  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let mut profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+
+    let profiles_dir = self.get_profiles_dir();
+    let user_js = profile.get_profile_data_path(&profiles_dir).join("user.js");
+
+    if enabled {
+      let port = pick_automation_port()?;
+      crate::profile::prefs::PrefStore::update(&user_js, |prefs| {
+        prefs.set("marionette.port", port as i64);
+        prefs.set("devtools.debugger.remote-enabled", true);
+        prefs.set("devtools.debugger.remote-port", port as i64);
+      })?;
+      profile.automation = Some(crate::profile::types::ProfileAutomation {
+        url: format!("ws://127.0.0.1:{}/session/", port),
+        port,
+      });
     } else {
-      Ok(profile.process_id.is_some())
+      crate::profile::prefs::PrefStore::update(&user_js, |prefs| {
+        prefs.remove("marionette.port");
+        prefs.remove("devtools.debugger.remote-enabled");
+        prefs.remove("devtools.debugger.remote-port");
+      })?;
+      profile.automation = None;
     }
+
+    self.save_profile(&profile)?;
+    let _ = events::emit_empty("profiles-changed");
+    Ok(profile)
+  }
+
+  /// Look up a profile's current automation endpoint, if automation is
+  /// enabled for it.
+  pub fn get_profile_automation_endpoint(
+    &self,
+    profile_id: &str,
+  ) -> Result<Option<crate::profile::types::ProfileAutomation>, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+    Ok(profile.automation)
+  }
+
+  /// Mark a profile as attached to a browser Foxia did not spawn — one
+  /// started manually, by CI, or by another tool — reachable on `port`.
+  /// Once set, `check_browser_status` tracks liveness via that port
+  /// instead of `process_id`, which the external process never populates.
+  pub fn attach_existing_browser(
+    &self,
+    _app_handle: &tauri::AppHandle,
+    profile_id: &str,
+    port: u16,
+  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let mut profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+    profile.attached_port = Some(port);
+    self.save_profile(&profile)?;
+    let _ = events::emit_empty("profiles-changed");
+    Ok(profile)
+  }
+
+  /// Clear a profile's `attached_port` without touching the external
+  /// process it pointed at — Foxia never owned its lifecycle, so detach
+  /// only stops tracking it.
+  pub fn detach_existing_browser(
+    &self,
+    _app_handle: &tauri::AppHandle,
+    profile_id: &str,
+  ) -> Result<BrowserProfile, Box<dyn std::error::Error>> {
+    let profile_uuid = uuid::Uuid::parse_str(profile_id)?;
+    let mut profile = self
+      .list_profiles()?
+      .into_iter()
+      .find(|p| p.id == profile_uuid)
+      .ok_or("Profile not found")?;
+    profile.attached_port = None;
+    self.save_profile(&profile)?;
+    let _ = events::emit_empty("profiles-changed");
+    Ok(profile)
   }
 
   pub fn clone_profile(
@@ -559,21 +862,49 @@ impl ProfileManager {
   ) -> Result<(), Box<dyn std::error::Error>> {
     let user_js = path.join("user.js");
     let p = internal.unwrap_or(proxy);
+    let protocol = ProxyProtocol::parse(&p.proxy_type);
+
+    // Merge into the existing user.js rather than overwriting it, so
+    // unrelated prefs (e.g. ones a fingerprint layer wrote) survive.
+    let mut prefs = crate::profile::prefs::PrefStore::load(&user_js)?;
+    prefs.set("network.proxy.type", 1i64);
+    prefs.set("network.proxy.share_proxy_settings", false);
+    prefs.set("network.proxy.no_proxies_on", "localhost, 127.0.0.1");
+
+    match protocol {
+      ProxyProtocol::Socks4 | ProxyProtocol::Socks5 => {
+        prefs.remove("network.proxy.http");
+        prefs.remove("network.proxy.http_port");
+        prefs.remove("network.proxy.ssl");
+        prefs.remove("network.proxy.ssl_port");
+        prefs.set("network.proxy.socks", p.host.as_str());
+        prefs.set("network.proxy.socks_port", p.port);
+        prefs.set("network.proxy.socks_version", if protocol == ProxyProtocol::Socks4 { 4i64 } else { 5i64 });
+        prefs.set("network.proxy.socks_remote_dns", true);
+      }
+      ProxyProtocol::Http | ProxyProtocol::Https => {
+        prefs.remove("network.proxy.socks");
+        prefs.remove("network.proxy.socks_port");
+        prefs.remove("network.proxy.socks_version");
+        prefs.set("network.proxy.http", p.host.as_str());
+        prefs.set("network.proxy.http_port", p.port);
+        prefs.set("network.proxy.ssl", p.host.as_str());
+        prefs.set("network.proxy.ssl_port", p.port);
+      }
+    }
+
+    // Firefox user.js has no pref for proxy credentials, so an
+    // authenticated proxy needs an auto-loaded WebExtension instead.
+    match (&p.username, &p.password) {
+      (Some(username), Some(password)) if !username.is_empty() => {
+        crate::profile::proxy_auth_extension::install(path, &mut prefs, username, password)?;
+      }
+      _ => {
+        crate::profile::proxy_auth_extension::uninstall(path)?;
+      }
+    }
 
-    let prefs = vec![
-      format!("user_pref(\"network.proxy.type\", 1);"),
-      format!("user_pref(\"network.proxy.share_proxy_settings\", true);"),
-      format!("user_pref(\"network.proxy.http\", \"{}\");", p.host),
-      format!("user_pref(\"network.proxy.http_port\", {});", p.port),
-      format!("user_pref(\"network.proxy.ssl\", \"{}\");", p.host),
-      format!("user_pref(\"network.proxy.ssl_port\", {});", p.port),
-      format!("user_pref(\"network.proxy.socks\", \"{}\");", p.host),
-      format!("user_pref(\"network.proxy.socks_port\", {});", p.port),
-      format!("user_pref(\"network.proxy.socks_remote_dns\", true);"),
-      format!("user_pref(\"network.proxy.no_proxies_on\", \"localhost, 127.0.0.1\");"),
-    ];
-
-    fs::write(user_js, prefs.join("\n"))?;
+    fs::write(&user_js, prefs.render())?;
     Ok(())
   }
 
@@ -582,7 +913,10 @@ impl ProfileManager {
     path: &Path,
   ) -> Result<(), Box<dyn std::error::Error>> {
     let user_js = path.join("user.js");
-    fs::write(user_js, "user_pref(\"network.proxy.type\", 0);")?;
+    crate::profile::prefs::PrefStore::update(&user_js, |prefs| {
+      prefs.set("network.proxy.type", 0i64);
+    })?;
+    crate::profile::proxy_auth_extension::uninstall(path)?;
     Ok(())
   }
 
@@ -633,6 +967,36 @@ impl ProfileManager {
   }
 }
 
+/// Ask the OS for an ephemeral loopback port by binding to port 0, then
+/// release it immediately so the caller can hand it to the browser
+/// instead. Racy in theory (something else could grab it first) but this
+/// is the same trick `TcpListener`-based port discovery always relies on.
+fn pick_automation_port() -> io::Result<u16> {
+  Ok(std::net::TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port())
+}
+
+/// Best-effort liveness probe: a live TCP connect to a loopback
+/// debugging/control port is a signal independent of whatever PID
+/// bookkeeping `process_id` carries (and the only one available for
+/// externally-launched, attached, or re-adopted browsers).
+async fn probe_tcp_port(port: u16) -> bool {
+  tokio::time::timeout(
+    tokio::time::Duration::from_millis(200),
+    tokio::net::TcpStream::connect(("127.0.0.1", port)),
+  )
+  .await
+  .is_ok_and(|r| r.is_ok())
+}
+
+/// Best-effort liveness probe for a profile's automation endpoint, if it
+/// has one.
+async fn probe_automation_port(profile: &BrowserProfile) -> bool {
+  let Some(automation) = &profile.automation else {
+    return false;
+  };
+  probe_tcp_port(automation.port).await
+}
+
 lazy_static::lazy_static! {
   static ref PROFILE_MANAGER: ProfileManager = ProfileManager::new();
 }
@@ -652,6 +1016,7 @@ pub async fn create_browser_profile_with_group(
   group_id: Option<String>,
   username: Option<String>,
   password: Option<String>,
+  proxy_settings: Option<ProxySettings>,
 ) -> Result<BrowserProfile, String> {
   ProfileManager::instance()
     .create_profile_with_group(
@@ -666,6 +1031,7 @@ pub async fn create_browser_profile_with_group(
       group_id,
       username,
       password,
+      proxy_settings,
     )
     .await
     .map_err(|e| e.to_string())
@@ -734,6 +1100,17 @@ pub fn update_profile_odoo_id(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn issue_profile_sync_macaroon(
+  app_handle: tauri::AppHandle,
+  profile_id: String,
+  ttl_secs: u64,
+) -> Result<BrowserProfile, String> {
+  ProfileManager::instance()
+    .issue_profile_sync_macaroon(&app_handle, &profile_id, ttl_secs)
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn check_browser_status(
   app_handle: tauri::AppHandle,
@@ -784,6 +1161,7 @@ pub async fn create_browser_profile_new(
   group_id: Option<String>,
   username: Option<String>,
   password: Option<String>,
+  proxy_settings: Option<ProxySettings>,
 ) -> Result<BrowserProfile, String> {
   let browser_type = BrowserType::from_str(&browser_str).map_err(|e| e.to_string())?;
   ProfileManager::instance()
@@ -799,6 +1177,7 @@ pub async fn create_browser_profile_new(
       group_id,
       username,
       password,
+      proxy_settings,
     )
     .await
     .map_err(|e| e.to_string())
@@ -842,6 +1221,47 @@ pub fn delete_profile(app_handle: tauri::AppHandle, profile_id: String) -> Resul
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn enable_profile_automation(
+  app_handle: tauri::AppHandle,
+  profile_id: String,
+  enabled: bool,
+) -> Result<BrowserProfile, String> {
+  ProfileManager::instance()
+    .enable_profile_automation(&app_handle, &profile_id, enabled)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_profile_automation_endpoint(
+  profile_id: String,
+) -> Result<Option<crate::profile::types::ProfileAutomation>, String> {
+  ProfileManager::instance()
+    .get_profile_automation_endpoint(&profile_id)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn attach_existing_browser(
+  app_handle: tauri::AppHandle,
+  profile_id: String,
+  port: u16,
+) -> Result<BrowserProfile, String> {
+  ProfileManager::instance()
+    .attach_existing_browser(&app_handle, &profile_id, port)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn detach_existing_browser(
+  app_handle: tauri::AppHandle,
+  profile_id: String,
+) -> Result<BrowserProfile, String> {
+  ProfileManager::instance()
+    .detach_existing_browser(&app_handle, &profile_id)
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn import_zsmkt_profiles_batch(
   app_handle: tauri::AppHandle,
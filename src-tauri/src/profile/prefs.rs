@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::Path;
+
+/// A single `user.js` value. Firefox prefs are always one of these three
+/// JS-literal shapes; tracking the type (rather than storing everything as
+/// a string) is what lets `PrefStore` round-trip `"8080"` vs `8080` vs
+/// `true` with the correct quoting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefValue {
+  Str(String),
+  Bool(bool),
+  Int(i64),
+}
+
+impl PrefValue {
+  fn to_literal(&self) -> String {
+    match self {
+      PrefValue::Str(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+      PrefValue::Bool(b) => b.to_string(),
+      PrefValue::Int(i) => i.to_string(),
+    }
+  }
+}
+
+impl From<&str> for PrefValue {
+  fn from(s: &str) -> Self {
+    PrefValue::Str(s.to_string())
+  }
+}
+impl From<String> for PrefValue {
+  fn from(s: String) -> Self {
+    PrefValue::Str(s)
+  }
+}
+impl From<bool> for PrefValue {
+  fn from(b: bool) -> Self {
+    PrefValue::Bool(b)
+  }
+}
+impl From<i64> for PrefValue {
+  fn from(i: i64) -> Self {
+    PrefValue::Int(i)
+  }
+}
+impl From<u16> for PrefValue {
+  fn from(i: u16) -> Self {
+    PrefValue::Int(i as i64)
+  }
+}
+
+enum Line {
+  Pref { key: String, value: PrefValue },
+  /// Anything that isn't a recognized `user_pref(...)` line (a comment, a
+  /// blank line, or something malformed) — passed through untouched.
+  Verbatim(String),
+}
+
+/// A parsed `user.js`: a structured `key -> value` map plus the original
+/// non-pref lines, so merging in a handful of keys (e.g. `network.proxy.*`)
+/// doesn't clobber whatever else a user or fingerprint layer wrote there.
+/// Mirrors the `PrefFile`/`Pref` split geckodriver's profile handling uses
+/// internally for the same reason.
+pub struct PrefStore {
+  lines: Vec<Line>,
+}
+
+impl PrefStore {
+  /// Load and parse `user.js` at `path`, or start empty if it doesn't
+  /// exist yet.
+  pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+    if !path.exists() {
+      return Ok(Self { lines: Vec::new() });
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(Self::parse(&content))
+  }
+
+  fn parse(content: &str) -> Self {
+    let mut lines = Vec::new();
+
+    for raw_line in content.lines() {
+      match parse_pref_line(raw_line) {
+        Some((key, value)) => {
+          // Dedupe repeated keys, keeping the last occurrence (and its
+          // original position) — later `user_pref` calls for the same key
+          // win in Firefox too.
+          lines.retain(|line| !matches!(line, Line::Pref { key: k, .. } if *k == key));
+          lines.push(Line::Pref { key, value });
+        }
+        None => lines.push(Line::Verbatim(raw_line.to_string())),
+      }
+    }
+
+    Self { lines }
+  }
+
+  /// Read back a currently-set pref, if any.
+  pub fn get(&self, key: &str) -> Option<&PrefValue> {
+    self.lines.iter().find_map(|line| match line {
+      Line::Pref { key: k, value } if k == key => Some(value),
+      _ => None,
+    })
+  }
+
+  /// Set `key` to `value`, replacing any existing entry for that key.
+  pub fn set(&mut self, key: impl Into<String>, value: impl Into<PrefValue>) {
+    let key = key.into();
+    let value = value.into();
+    if let Some(existing) = self.lines.iter_mut().find_map(|line| match line {
+      Line::Pref { key: k, value: v } if *k == key => Some(v),
+      _ => None,
+    }) {
+      *existing = value;
+    } else {
+      self.lines.push(Line::Pref { key, value });
+    }
+  }
+
+  /// Remove `key` entirely, if present.
+  pub fn remove(&mut self, key: &str) {
+    self.lines.retain(|line| !matches!(line, Line::Pref { key: k, .. } if k == key));
+  }
+
+  /// Serialize back to `user.js` text, preserving the untouched lines.
+  pub fn render(&self) -> String {
+    self
+      .lines
+      .iter()
+      .map(|line| match line {
+        Line::Pref { key, value } => format!("user_pref(\"{}\", {});", key, value.to_literal()),
+        Line::Verbatim(raw) => raw.clone(),
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  /// Parse, mutate via `f`, and write back to `path` in one step.
+  pub fn update(path: &Path, f: impl FnOnce(&mut PrefStore)) -> Result<(), Box<dyn std::error::Error>> {
+    let mut store = Self::load(path)?;
+    f(&mut store);
+    fs::write(path, store.render())?;
+    Ok(())
+  }
+}
+
+/// Parse a single `user_pref("key", value);` line. Returns `None` for
+/// anything else (comments, blank lines, malformed syntax), so the caller
+/// can pass it through as-is rather than lose or corrupt it.
+fn parse_pref_line(line: &str) -> Option<(String, PrefValue)> {
+  let trimmed = line.trim();
+  let inner = trimmed.strip_prefix("user_pref(")?;
+  let inner = inner.strip_suffix(");")?.trim();
+
+  let (key_part, value_part) = split_first_arg(inner)?;
+
+  let key = key_part.trim();
+  let key = key.strip_prefix('"')?.strip_suffix('"')?.to_string();
+
+  let value_part = value_part.trim();
+  let value = if let Some(s) = value_part.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+    PrefValue::Str(s.replace("\\\"", "\"").replace("\\\\", "\\"))
+  } else if value_part == "true" {
+    PrefValue::Bool(true)
+  } else if value_part == "false" {
+    PrefValue::Bool(false)
+  } else {
+    PrefValue::Int(value_part.parse().ok()?)
+  };
+
+  Some((key, value))
+}
+
+/// Split `"key", value` on the first top-level comma (outside the quoted
+/// key), since the value itself may legitimately contain commas inside a
+/// quoted string.
+fn split_first_arg(inner: &str) -> Option<(&str, &str)> {
+  let mut in_quotes = false;
+  let mut escaped = false;
+  let bytes = inner.as_bytes();
+  for (i, &b) in bytes.iter().enumerate() {
+    match b {
+      b'\\' if in_quotes => escaped = !escaped,
+      b'"' if !escaped => in_quotes = !in_quotes,
+      b',' if !in_quotes => return Some((&inner[..i], &inner[i + 1..])),
+      _ => escaped = false,
+    }
+  }
+  None
+}
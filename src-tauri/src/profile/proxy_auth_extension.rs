@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::Path;
+
+/// Firefox `user.js` has no pref for proxy username/password, so an
+/// authenticated proxy needs an extension that answers
+/// `webRequest.onAuthRequired` instead. This generates that extension as
+/// an unpacked directory under `<profile>/extensions/<EXTENSION_ID>/` and
+/// flips the prefs needed to auto-load an unsigned, unpacked extension.
+const EXTENSION_ID: &str = "proxy-auth@foxia-mkt.internal";
+
+/// Install (or overwrite) the proxy-auth extension in `profile_data_dir`,
+/// configured to answer auth challenges with `username`/`password`, and
+/// set the prefs Firefox needs to load an unpacked, unsigned extension
+/// automatically on next launch.
+pub fn install(
+  profile_data_dir: &Path,
+  prefs: &mut crate::profile::prefs::PrefStore,
+  username: &str,
+  password: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+  let ext_dir = profile_data_dir.join("extensions").join(EXTENSION_ID);
+  fs::create_dir_all(&ext_dir)?;
+
+  fs::write(ext_dir.join("manifest.json"), manifest_json())?;
+  fs::write(ext_dir.join("background.js"), background_js(username, password))?;
+
+  // Let the unpacked, unsigned extension above load without going through
+  // AMO signing — standard for profile-bundled automation extensions.
+  prefs.set("xpinstall.signatures.required", false);
+  prefs.set("extensions.autoDisableScopes", 0i64);
+
+  Ok(())
+}
+
+/// Remove a previously-installed proxy-auth extension, if any (e.g. when
+/// the proxy is disabled or its credentials are cleared).
+pub fn uninstall(profile_data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+  let ext_dir = profile_data_dir.join("extensions").join(EXTENSION_ID);
+  if ext_dir.exists() {
+    fs::remove_dir_all(&ext_dir)?;
+  }
+  Ok(())
+}
+
+fn manifest_json() -> String {
+  serde_json::json!({
+    "manifest_version": 2,
+    "name": "Foxia Proxy Auth",
+    "version": "1.0",
+    "description": "Supplies proxy credentials for an authenticated upstream proxy.",
+    "applications": { "gecko": { "id": EXTENSION_ID } },
+    "permissions": ["webRequest", "webRequestBlocking", "proxy", "<all_urls>"],
+    "background": { "scripts": ["background.js"] },
+  })
+  .to_string()
+}
+
+/// Credentials are baked directly into the generated script rather than
+/// read from storage, since the extension is regenerated (not updated)
+/// whenever the proxy credentials change.
+fn background_js(username: &str, password: &str) -> String {
+  format!(
+    r#"browser.webRequest.onAuthRequired.addListener(
+  () => ({{ authCredentials: {{ username: {username}, password: {password} }} }}),
+  {{ urls: ["<all_urls>"] }},
+  ["blocking"]
+);
+"#,
+    username = serde_json::to_string(username).unwrap_or_else(|_| "\"\"".to_string()),
+    password = serde_json::to_string(password).unwrap_or_else(|_| "\"\"".to_string()),
+  )
+}
@@ -0,0 +1,98 @@
+use crate::profile::manager::ProfileManager;
+use std::path::Path;
+
+fn resolve_profile_data_dir(profile_id: &str) -> Result<std::path::PathBuf, String> {
+  let profile_manager = ProfileManager::instance();
+  let profiles_dir = profile_manager.get_profiles_dir();
+
+  let profile_uuid = uuid::Uuid::parse_str(profile_id).map_err(|e| e.to_string())?;
+  let profiles = profile_manager.list_profiles().map_err(|e| e.to_string())?;
+  let profile = profiles
+    .iter()
+    .find(|p| p.id == profile_uuid)
+    .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+
+  Ok(profile.get_profile_data_path(&profiles_dir))
+}
+
+/// Apply the env-sanitized variables to a `Command` about to spawn the
+/// system file manager / finder, so it doesn't inherit bundle library
+/// paths (see `env_sanitize`).
+#[cfg(not(target_os = "linux"))]
+fn sanitize(cmd: &mut std::process::Command) {
+  cmd.envs(crate::env_sanitize::sanitized_env());
+}
+
+#[cfg(target_os = "linux")]
+fn open_dir(path: &Path) -> Result<(), String> {
+  let uri = format!("file://{}", path.display());
+  let file = gio::File::for_uri(&uri);
+  gio::AppInfo::launch_default_for_uri(&file.uri(), gio::AppLaunchContext::NONE)
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn reveal_dir(path: &Path) -> Result<(), String> {
+  use zbus::blocking::Connection;
+
+  let connection = Connection::session().map_err(|e| e.to_string())?;
+  let uri = format!("file://{}", path.display());
+
+  connection
+    .call_method(
+      Some("org.freedesktop.FileManager1"),
+      "/org/freedesktop/FileManager1",
+      Some("org.freedesktop.FileManager1"),
+      "ShowItems",
+      &(vec![uri], "foxia".to_string()),
+    )
+    .map_err(|e| e.to_string())?;
+
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn open_dir(path: &Path) -> Result<(), String> {
+  let mut cmd = std::process::Command::new("open");
+  sanitize(&mut cmd);
+  cmd.arg(path).status().map_err(|e| e.to_string())?;
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_dir(path: &Path) -> Result<(), String> {
+  let mut cmd = std::process::Command::new("open");
+  sanitize(&mut cmd);
+  cmd.arg("-R").arg(path).status().map_err(|e| e.to_string())?;
+  Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn open_dir(path: &Path) -> Result<(), String> {
+  let mut cmd = std::process::Command::new("explorer");
+  sanitize(&mut cmd);
+  cmd.arg(path).status().map_err(|e| e.to_string())?;
+  Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn reveal_dir(path: &Path) -> Result<(), String> {
+  let mut arg = std::ffi::OsString::from("/select,");
+  arg.push(path.as_os_str());
+  let mut cmd = std::process::Command::new("explorer");
+  sanitize(&mut cmd);
+  cmd.arg(arg).status().map_err(|e| e.to_string())?;
+  Ok(())
+}
+
+#[tauri::command]
+pub fn open_profile_dir(profile_id: String) -> Result<(), String> {
+  let path = resolve_profile_data_dir(&profile_id)?;
+  open_dir(&path)
+}
+
+#[tauri::command]
+pub fn reveal_profile_dir(profile_id: String) -> Result<(), String> {
+  let path = resolve_profile_data_dir(&profile_id)?;
+  reveal_dir(&path)
+}
@@ -0,0 +1,206 @@
+use crate::profile::manager::ProfileManager;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEYRING_SERVICE: &str = "foxia-mkt";
+const KEYRING_ACCOUNT: &str = "signing-key";
+const KNOWN_SIGNERS_FILE: &str = "known_signers.json";
+
+#[derive(Debug)]
+pub struct SigningError(String);
+
+impl fmt::Display for SigningError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "bundle signature error: {}", self.0)
+  }
+}
+impl std::error::Error for SigningError {}
+
+/// Look up (or lazily create) this install's Ed25519 signing seed in the
+/// OS keyring, mirroring `crate::vault::vault_key` / `crate::macaroon::root_key`
+/// but storing a signing seed instead of a symmetric key. Falls back to
+/// deriving a seed via Argon2id from `FOXIA_SIGNING_KEY_SEED` when no
+/// keyring is available (e.g. headless CI); with that unset there is no
+/// source-visible seed to fall back to, so this fails loudly instead of
+/// deriving a publicly-known signing identity every install would share.
+fn signing_key() -> Result<SigningKey, SigningError> {
+  if let Ok(entry) = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT) {
+    if let Ok(existing) = entry.get_password() {
+      if let Ok(bytes) = STANDARD.decode(existing) {
+        if bytes.len() == 32 {
+          let mut seed = [0u8; 32];
+          seed.copy_from_slice(&bytes);
+          return Ok(SigningKey::from_bytes(&seed));
+        }
+      }
+    }
+
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+    let _ = entry.set_password(&STANDARD.encode(seed));
+    return Ok(SigningKey::from_bytes(&seed));
+  }
+
+  const SALT: &[u8] = b"foxia-mkt-signing-salt-v1";
+  let passphrase = std::env::var("FOXIA_SIGNING_KEY_SEED").map_err(|_| {
+    SigningError(
+      "no OS keyring available and FOXIA_SIGNING_KEY_SEED is unset; refusing to derive a signing key from a hardcoded passphrase".to_string(),
+    )
+  })?;
+  let mut seed = [0u8; 32];
+  argon2::Argon2::default()
+    .hash_password_into(passphrase.as_bytes(), SALT, &mut seed)
+    .expect("Argon2id key derivation must not fail for a fixed-size output");
+  Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Overwrite the install's signing keypair with a freshly generated one,
+/// returning the new key's id. Bundles this install already exported
+/// remain verifiable by whoever received them (the signature was over
+/// the old key), but this install can no longer produce that signature
+/// again after rotating.
+fn regenerate_signing_key() -> Result<String, SigningError> {
+  let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT).map_err(|e| SigningError(e.to_string()))?;
+  let mut seed = [0u8; 32];
+  OsRng.fill_bytes(&mut seed);
+  entry
+    .set_password(&STANDARD.encode(seed))
+    .map_err(|e| SigningError(e.to_string()))?;
+  Ok(key_id(&SigningKey::from_bytes(&seed).verifying_key()))
+}
+
+/// Short, stable identifier for a public key, so a manifest or a
+/// known-signers list can name "who signed this" without embedding the
+/// full key inline every time it's displayed.
+fn key_id(verifying_key: &VerifyingKey) -> String {
+  STANDARD.encode(&verifying_key.to_bytes()[..8])
+}
+
+/// Embedded in a shared bundle's manifest: which install signed it, and
+/// the proof. `verify` checks `signature` against `public_key` directly
+/// rather than trusting `key_id` as anything more than a display label,
+/// so a stale `known_signers` entry can't be used to smuggle a different
+/// key's signature past verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureBlock {
+  pub key_id: String,
+  pub public_key: String,
+  pub signature: String,
+}
+
+/// Sign `payload` (a bundle's canonical, signature-stripped manifest
+/// bytes) with this install's signing key.
+pub fn sign(payload: &[u8]) -> Result<SignatureBlock, SigningError> {
+  let key = signing_key()?;
+  let verifying_key = key.verifying_key();
+  let signature = key.sign(payload);
+  Ok(SignatureBlock {
+    key_id: key_id(&verifying_key),
+    public_key: STANDARD.encode(verifying_key.to_bytes()),
+    signature: STANDARD.encode(signature.to_bytes()),
+  })
+}
+
+/// Verify `block.signature` over `payload` under `block.public_key`.
+pub fn verify(payload: &[u8], block: &SignatureBlock) -> Result<(), SigningError> {
+  let public_bytes = STANDARD
+    .decode(&block.public_key)
+    .map_err(|e| SigningError(e.to_string()))?;
+  let public_bytes: [u8; 32] = public_bytes
+    .try_into()
+    .map_err(|_| SigningError("public key is not 32 bytes".to_string()))?;
+  let verifying_key = VerifyingKey::from_bytes(&public_bytes).map_err(|e| SigningError(e.to_string()))?;
+
+  let sig_bytes = STANDARD
+    .decode(&block.signature)
+    .map_err(|e| SigningError(e.to_string()))?;
+  let sig_bytes: [u8; 64] = sig_bytes
+    .try_into()
+    .map_err(|_| SigningError("signature is not 64 bytes".to_string()))?;
+
+  verifying_key
+    .verify(payload, &ed25519_dalek::Signature::from_bytes(&sig_bytes))
+    .map_err(|e| SigningError(e.to_string()))
+}
+
+/// A signer this install has seen a validly-signed bundle from before,
+/// recorded the first time `import_profile_pack` verifies one of its
+/// signatures so a later import can tell "seen before" from "first
+/// contact" when surfacing the signer identity to the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownSigner {
+  pub key_id: String,
+  pub public_key: String,
+  pub first_seen_unix: u64,
+}
+
+fn known_signers_path(signing_dir: &Path) -> PathBuf {
+  signing_dir.join(KNOWN_SIGNERS_FILE)
+}
+
+fn load_known_signers(signing_dir: &Path) -> Result<Vec<KnownSigner>, Box<dyn std::error::Error>> {
+  let path = known_signers_path(signing_dir);
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  let contents = fs::read_to_string(path)?;
+  Ok(serde_json::from_str(&contents).unwrap_or_default())
+}
+
+fn save_known_signers(signing_dir: &Path, signers: &[KnownSigner]) -> Result<(), Box<dyn std::error::Error>> {
+  fs::create_dir_all(signing_dir)?;
+  fs::write(known_signers_path(signing_dir), serde_json::to_string_pretty(signers)?)?;
+  Ok(())
+}
+
+impl ProfileManager {
+  /// Rotate this install's signing keypair and return the new key id.
+  pub fn generate_signing_key(&self) -> Result<String, Box<dyn std::error::Error>> {
+    regenerate_signing_key().map_err(|e| e.into())
+  }
+
+  /// Every signer this install has ever seen a validly-signed bundle
+  /// from, most useful for letting a user build a personal allowlist of
+  /// trusted teammates' key ids.
+  pub fn list_known_signers(&self) -> Result<Vec<KnownSigner>, Box<dyn std::error::Error>> {
+    load_known_signers(&self.get_signing_dir())
+  }
+
+  /// Record `key_id`/`public_key` as a known signer the first time a
+  /// bundle signed with it verifies successfully. Idempotent: a key
+  /// already on record is left untouched rather than bumping its
+  /// `first_seen_unix`.
+  pub(crate) fn remember_signer(&self, key_id: &str, public_key: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let signing_dir = self.get_signing_dir();
+    let mut signers = load_known_signers(&signing_dir)?;
+    if signers.iter().any(|s| s.key_id == key_id) {
+      return Ok(());
+    }
+    signers.push(KnownSigner {
+      key_id: key_id.to_string(),
+      public_key: public_key.to_string(),
+      first_seen_unix: std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0),
+    });
+    save_known_signers(&signing_dir, &signers)
+  }
+}
+
+#[tauri::command]
+pub fn generate_signing_key() -> Result<String, String> {
+  ProfileManager::instance().generate_signing_key().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_known_signers() -> Result<Vec<KnownSigner>, String> {
+  ProfileManager::instance().list_known_signers().map_err(|e| e.to_string())
+}
@@ -50,15 +50,52 @@ pub struct BrowserProfile {
   #[serde(default)]
   pub odoo_proxy: Option<crate::odoo::types::OdooProxy>,
   #[serde(default)]
-  pub username: Option<String>,
+  pub username: Option<crate::vault::SealedSecretString>,
   #[serde(default)]
-  pub password: Option<String>,
+  pub password: Option<crate::vault::SealedSecretString>,
   #[serde(default)]
   pub user_agent: Option<String>,
+  /// Port of the geckodriver/Marionette instance driving this profile's
+  /// browser, if it was launched with remote automation enabled. Lets
+  /// callers obtain a `crate::webdriver::WebDriverClient` without having
+  /// to rediscover the port out-of-band.
+  #[serde(default)]
+  pub webdriver_port: Option<u16>,
+  /// Serialized `crate::macaroon::Macaroon` granting time-boxed sync
+  /// rights for this profile, issued in place of sharing the master Odoo
+  /// session/password with a sync coordinator. Verified client-side
+  /// before any sync request is issued.
+  #[serde(default)]
+  pub sync_macaroon: Option<String>,
+  /// Remote-control endpoint exposed while this profile is launched with
+  /// automation enabled, populated by `enable_profile_automation`. Lets a
+  /// script attach to a running Foxia profile the way a WebDriver BiDi
+  /// client attaches to a geckodriver session.
+  #[serde(default)]
+  pub automation: Option<ProfileAutomation>,
+  /// Debugging port of a browser Foxia did not itself spawn (started
+  /// manually, by CI, etc.), set by `attach_existing_browser`. Mirrors
+  /// geckodriver's `Browser::Existing(u16)` variant: while set,
+  /// `check_browser_status` probes this port instead of `process_id`,
+  /// and `detach_existing_browser` clears it without touching the
+  /// external process.
+  #[serde(default)]
+  pub attached_port: Option<u16>,
   #[serde(skip_deserializing)]
   pub absolute_path: Option<String>,
 }
 
+/// A profile's bidirectional remote-automation endpoint: the
+/// Marionette/CDP port written into its `user.js` (`marionette.port` /
+/// `devtools.debugger.remote-port`) and the control URL a WebDriver BiDi or
+/// CDP client connects to, mirroring geckodriver's `webSocketUrl`
+/// capability.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProfileAutomation {
+  pub url: String,
+  pub port: u16,
+}
+
 pub fn default_release_type() -> String {
   "stable".to_string()
 }
@@ -170,9 +170,13 @@ pub fn convert_zsmkt_profile(zs_profile: ZsMktProfile, proxy_id: Option<String>)
     profile_url: zs_profile.profile_url,
     created_at: created_at.or(Some(chrono::Utc::now().timestamp() as u64)),
     odoo_proxy: None,
-    username: zs_profile.username,
-    password: zs_profile.password,
+    username: zs_profile.username.map(crate::vault::SealedSecretString::new),
+    password: zs_profile.password.map(crate::vault::SealedSecretString::new),
     user_agent: None,
+    webdriver_port: None,
+    sync_macaroon: None,
+    automation: None,
     absolute_path: None,
+    attached_port: None,
   }
 }
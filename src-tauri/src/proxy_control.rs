@@ -0,0 +1,67 @@
+use crate::proxy_storage::{get_proxy_config, save_proxy_config};
+use serde_json::json;
+
+/// Path to the worker's admin control socket, derived from its id so the
+/// main process and the worker agree on it without needing extra
+/// bookkeeping.
+#[cfg(unix)]
+fn control_socket_path(id: &str) -> std::path::PathBuf {
+  std::path::PathBuf::from("/tmp").join(format!("foxia-proxy-{}.ctl", id))
+}
+
+#[cfg(windows)]
+fn control_pipe_path(id: &str) -> String {
+  format!(r"\\.\pipe\foxia-proxy-{}", id)
+}
+
+/// Hot-reload a running proxy worker's upstream without tearing down the
+/// listener: sends `{"cmd":"set_upstream","url":...}` over the worker's
+/// admin control channel. The worker is expected to hold its upstream
+/// behind an `Arc<RwLock<UpstreamAddress>>` read on each new connection, so
+/// the swap applies to all future connections while existing ones keep
+/// using whatever upstream they already resolved.
+pub async fn update_proxy_upstream(
+  id: &str,
+  new_upstream_url: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+  let config = get_proxy_config(id).ok_or_else(|| format!("Unknown proxy: {}", id))?;
+
+  let message = json!({
+    "cmd": "set_upstream",
+    "url": new_upstream_url,
+  })
+  .to_string();
+
+  #[cfg(unix)]
+  {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::UnixStream;
+
+    let path = control_socket_path(id);
+    let mut stream = UnixStream::connect(&path)
+      .await
+      .map_err(|e| format!("Could not reach control socket for proxy {}: {}", id, e))?;
+    stream.write_all(message.as_bytes()).await?;
+    stream.write_all(b"\n").await?;
+  }
+
+  #[cfg(windows)]
+  {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::windows::named_pipe::ClientOptions;
+
+    let path = control_pipe_path(id);
+    let mut client = ClientOptions::new()
+      .open(&path)
+      .map_err(|e| format!("Could not reach control pipe for proxy {}: {}", id, e))?;
+    client.write_all(message.as_bytes()).await?;
+    client.write_all(b"\n").await?;
+  }
+
+  let mut updated = config;
+  updated.upstream = new_upstream_url.to_string();
+  save_proxy_config(&updated)?;
+
+  log::info!("Hot-reloaded upstream for proxy {} -> {}", id, new_upstream_url);
+  Ok(())
+}
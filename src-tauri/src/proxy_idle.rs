@@ -0,0 +1,56 @@
+use crate::proxy_runner::stop_proxy_process;
+use crate::proxy_storage::list_proxy_configs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+/// Whether a proxy has been idle longer than its configured TTL. Only the
+/// TTL is checked here; `spawn_idle_reaper` separately skips proxies with
+/// an attached running profile. Proxies pinned with `idle_ttl_secs = None`
+/// never time out.
+fn is_idle_timed_out(config: &crate::proxy_storage::ProxyConfig) -> bool {
+  let Some(idle_ttl_secs) = config.idle_ttl_secs else {
+    return false;
+  };
+  let last_active = config.last_active.unwrap_or(0);
+  now_unix().saturating_sub(last_active) > idle_ttl_secs
+}
+
+/// Background task that periodically scans persisted proxy configs and
+/// tears down any worker that has been idle past its `idle_ttl_secs` and
+/// has no attached profile/browser process, reclaiming resources that
+/// would otherwise accumulate across a long-running session.
+pub async fn spawn_idle_reaper() {
+  loop {
+    tokio::time::sleep(Duration::from_secs(30)).await;
+
+    for config in list_proxy_configs() {
+      let browser_attached = config.profile_id.as_deref().is_some_and(|profile_id| {
+        matches!(
+          crate::daemon::supervisor::profile_state(profile_id),
+          Some(crate::daemon::supervisor::ProcessState::Running)
+        )
+      });
+      if browser_attached {
+        continue;
+      }
+      if !is_idle_timed_out(&config) {
+        continue;
+      }
+
+      log::info!(
+        "Proxy {} idle for longer than {:?}s with no attached profile, reaping",
+        config.id,
+        config.idle_ttl_secs
+      );
+      if let Err(e) = stop_proxy_process(&config.id).await {
+        log::error!("Failed to reap idle proxy {}: {}", config.id, e);
+      }
+    }
+  }
+}
@@ -0,0 +1,63 @@
+use crate::proxy_storage::{delete_proxy_config, is_process_running, list_proxy_configs};
+
+/// At app launch, `PROXY_PROCESSES` (the in-memory id -> pid map used by
+/// `proxy_runner`) is always empty even though detached workers from the
+/// previous run may still be alive. Re-adopt any worker that's still
+/// listening on its saved port, and clean up anything that isn't so
+/// `stop_all_proxy_processes` can manage processes it didn't personally
+/// spawn.
+pub async fn reconcile_proxies_on_startup() {
+  for config in list_proxy_configs() {
+    let process_alive = config.pid.map(is_process_running).unwrap_or(false);
+
+    let port_listening = match config.local_port {
+      Some(port) => tokio::time::timeout(
+        tokio::time::Duration::from_millis(200),
+        tokio::net::TcpStream::connect(("127.0.0.1", port)),
+      )
+      .await
+      .is_ok_and(|r| r.is_ok()),
+      None => false,
+    };
+
+    if process_alive && port_listening {
+      if let Some(pid) = config.pid {
+        crate::proxy_runner::adopt_proxy_process(config.id.clone(), pid);
+        log::info!(
+          "Re-adopted surviving proxy worker {} (pid {})",
+          config.id,
+          pid
+        );
+      }
+      continue;
+    }
+
+    // Stale orphan: kill it if it happens to be alive but unresponsive,
+    // then drop the config entirely.
+    if let Some(pid) = config.pid {
+      if process_alive {
+        log::warn!(
+          "Proxy {} (pid {}) is alive but not listening, killing orphan",
+          config.id,
+          pid
+        );
+        #[cfg(unix)]
+        {
+          let _ = std::process::Command::new("kill")
+            .arg("-KILL")
+            .arg(pid.to_string())
+            .output();
+        }
+        #[cfg(windows)]
+        {
+          let _ = std::process::Command::new("taskkill")
+            .args(["/F", "/PID", &pid.to_string()])
+            .output();
+        }
+      }
+    }
+
+    log::info!("Discarding stale proxy config {}", config.id);
+    delete_proxy_config(&config.id);
+  }
+}
@@ -8,6 +8,29 @@ lazy_static::lazy_static! {
     std::sync::Mutex::new(std::collections::HashMap::new());
 }
 
+/// Re-insert an id -> pid mapping into the in-memory process table for a
+/// worker this run didn't spawn itself (e.g. one surviving from a previous
+/// app launch). See `proxy_reconcile::reconcile_proxies_on_startup`.
+pub fn adopt_proxy_process(id: String, pid: u32) {
+  let mut processes = PROXY_PROCESSES.lock().unwrap();
+  processes.insert(id, pid);
+}
+
+/// Controls whether a spawned proxy worker outlives its parent.
+///
+/// `Detached` (the default) keeps today's behavior: the worker is put in
+/// its own session (`setsid` / `DETACHED_PROCESS`) so it survives the main
+/// app crashing, which is deliberate for long-lived background proxies but
+/// is also what causes orphan accumulation. `TiedToParent` opts into
+/// guaranteed cleanup instead, for short-lived/foreground sessions that
+/// would rather lose their proxy than leak it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessLifetime {
+  #[default]
+  Detached,
+  TiedToParent,
+}
+
 pub async fn start_proxy_process(
   upstream_url: Option<String>,
   port: Option<u16>,
@@ -20,7 +43,37 @@ pub async fn start_proxy_process_with_profile(
   port: Option<u16>,
   profile_id: Option<String>,
 ) -> Result<ProxyConfig, Box<dyn std::error::Error>> {
-  let id = generate_proxy_id();
+  start_proxy_process_with_lifetime(upstream_url, port, profile_id, ProcessLifetime::Detached).await
+}
+
+pub async fn start_proxy_process_with_lifetime(
+  upstream_url: Option<String>,
+  port: Option<u16>,
+  profile_id: Option<String>,
+  lifetime: ProcessLifetime,
+) -> Result<ProxyConfig, Box<dyn std::error::Error>> {
+  start_proxy_process_with_id(
+    generate_proxy_id(),
+    upstream_url,
+    port,
+    profile_id,
+    lifetime,
+  )
+  .await
+}
+
+/// Same as [`start_proxy_process_with_lifetime`] but reuses a caller-supplied
+/// id instead of minting a new one via `generate_proxy_id`. Used by
+/// `proxy_supervisor` to respawn a crashed worker under its original id so
+/// the restart updates the existing `ProxyConfig` in place instead of
+/// orphaning it and persisting a second, new-id entry.
+pub async fn start_proxy_process_with_id(
+  id: String,
+  upstream_url: Option<String>,
+  port: Option<u16>,
+  profile_id: Option<String>,
+  lifetime: ProcessLifetime,
+) -> Result<ProxyConfig, Box<dyn std::error::Error>> {
   let upstream = upstream_url.unwrap_or_else(|| "DIRECT".to_string());
 
   // Dùng port=0 để process con tự bind vào port available
@@ -69,7 +122,7 @@ pub async fn start_proxy_process_with_profile(
 
     // Properly detach the process on Unix by creating a new session
     unsafe {
-      cmd.pre_exec(|| {
+      cmd.pre_exec(move || {
         // Create a new process group so the process survives parent exit
         libc::setsid();
 
@@ -79,6 +132,12 @@ pub async fn start_proxy_process_with_profile(
           let _ = libc::setpriority(libc::PRIO_PROCESS, 0, -5);
         }
 
+        // Managed-lifetime mode: ask the kernel to signal us if the parent
+        // dies, so the worker doesn't outlive a crashed main process.
+        if lifetime == ProcessLifetime::TiedToParent {
+          libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM);
+        }
+
         Ok(())
       });
     }
@@ -151,6 +210,35 @@ pub async fn start_proxy_process_with_profile(
       }
     }
 
+    // Managed-lifetime mode: assign the worker to a Job Object with
+    // JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE so Windows terminates it
+    // automatically if the main process (and thus the job handle) dies.
+    if lifetime == ProcessLifetime::TiedToParent {
+      use windows::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, SetInformationJobObject,
+        JobObjectExtendedLimitInformation, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+      };
+      use windows::Win32::System::Threading::PROCESS_TERMINATE;
+
+      unsafe {
+        if let Ok(job) = CreateJobObjectW(None, None) {
+          let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+          info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+          let _ = SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &info as *const _ as *const std::ffi::c_void,
+            std::mem::size_of_val(&info) as u32,
+          );
+          if let Ok(handle) = OpenProcess(PROCESS_TERMINATE, false, pid) {
+            let _ = AssignProcessToJobObject(job, handle);
+            let _ = CloseHandle(handle);
+          }
+        }
+      }
+    }
+
     // Store PID
     {
       let mut processes = PROXY_PROCESSES.lock().unwrap();
@@ -262,44 +350,107 @@ pub async fn start_proxy_process_with_profile(
   }
 }
 
+const STOP_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+const STOP_GRACE_DEADLINE: std::time::Duration = std::time::Duration::from_secs(3);
+
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: &str) {
+  use std::process::Command;
+  let _ = Command::new("kill").arg(signal).arg(pid.to_string()).output();
+}
+
+#[cfg(windows)]
+fn send_graceful(pid: u32) {
+  use std::process::Command;
+  // Plain taskkill (no /F) requests a graceful close first.
+  let _ = Command::new("taskkill")
+    .args(["/PID", &pid.to_string()])
+    .output();
+}
+
+#[cfg(windows)]
+fn send_force_kill(pid: u32) {
+  use std::process::Command;
+  let _ = Command::new("taskkill")
+    .args(["/F", "/PID", &pid.to_string()])
+    .output();
+}
+
+/// Reap a direct child so it doesn't become a zombie once it exits. If the
+/// worker wasn't spawned as a direct child of this process (e.g. re-adopted
+/// across a restart), there's nothing for us to reap and this is a no-op.
+#[cfg(unix)]
+fn reap_if_child(pid: u32) {
+  unsafe {
+    let mut status = 0i32;
+    loop {
+      let ret = libc::waitpid(pid as libc::pid_t, &mut status, libc::WNOHANG);
+      if ret <= 0 {
+        break;
+      }
+    }
+  }
+}
+
+/// Stop a proxy worker with a kill-and-reap discipline: send a graceful
+/// signal first, poll for actual death up to a deadline, escalate to a
+/// forceful kill if it didn't die in time, and only remove bookkeeping
+/// (`PROXY_PROCESSES` entry + persisted config) once death is confirmed.
 pub async fn stop_proxy_process(id: &str) -> Result<bool, Box<dyn std::error::Error>> {
   let config = get_proxy_config(id);
 
-  if let Some(config) = config {
-    if let Some(pid) = config.pid {
-      // Kill the process
-      #[cfg(unix)]
-      {
-        use std::process::Command;
-        let _ = Command::new("kill")
-          .arg("-TERM")
-          .arg(pid.to_string())
-          .output();
-      }
-      #[cfg(windows)]
-      {
-        use std::process::Command;
-        let _ = Command::new("taskkill")
-          .args(["/F", "/PID", &pid.to_string()])
-          .output();
-      }
+  let Some(config) = config else {
+    return Ok(false);
+  };
+  let Some(pid) = config.pid else {
+    return Ok(false);
+  };
 
-      // Wait a bit for the process to exit
-      tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+  #[cfg(unix)]
+  send_signal(pid, "-TERM");
+  #[cfg(windows)]
+  send_graceful(pid);
 
-      // Remove from tracking
-      {
-        let mut processes = PROXY_PROCESSES.lock().unwrap();
-        processes.remove(id);
-      }
+  let deadline = std::time::Instant::now() + STOP_GRACE_DEADLINE;
+  let mut died = !is_process_running(pid);
+  while !died && std::time::Instant::now() < deadline {
+    tokio::time::sleep(STOP_POLL_INTERVAL).await;
+    died = !is_process_running(pid);
+  }
 
-      // Delete the config file
-      delete_proxy_config(id);
-      return Ok(true);
+  if !died {
+    log::warn!(
+      "Proxy {} (pid {}) did not exit after SIGTERM within {:?}, escalating to SIGKILL",
+      id,
+      pid,
+      STOP_GRACE_DEADLINE
+    );
+    #[cfg(unix)]
+    send_signal(pid, "-KILL");
+    #[cfg(windows)]
+    send_force_kill(pid);
+
+    let kill_deadline = std::time::Instant::now() + STOP_GRACE_DEADLINE;
+    died = !is_process_running(pid);
+    while !died && std::time::Instant::now() < kill_deadline {
+      tokio::time::sleep(STOP_POLL_INTERVAL).await;
+      died = !is_process_running(pid);
     }
   }
 
-  Ok(false)
+  #[cfg(unix)]
+  reap_if_child(pid);
+
+  if !died {
+    return Err(format!("Failed to kill proxy {} (pid {})", id, pid).into());
+  }
+
+  {
+    let mut processes = PROXY_PROCESSES.lock().unwrap();
+    processes.remove(id);
+  }
+  delete_proxy_config(id);
+  Ok(true)
 }
 
 pub async fn stop_all_proxy_processes() -> Result<(), Box<dyn std::error::Error>> {
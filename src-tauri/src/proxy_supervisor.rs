@@ -0,0 +1,106 @@
+use crate::proxy_runner::{start_proxy_process_with_id, ProcessLifetime};
+use crate::proxy_storage::{is_process_running, list_proxy_configs};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_RESTARTS: u32 = 10;
+
+struct RestartState {
+  restarts: u32,
+  next_attempt_at: Instant,
+  quarantined: bool,
+}
+
+impl RestartState {
+  fn fresh() -> Self {
+    Self {
+      restarts: 0,
+      next_attempt_at: Instant::now(),
+      quarantined: false,
+    }
+  }
+
+  fn backoff(&self) -> Duration {
+    let shift = self.restarts.min(5); // 1,2,4,8,16,32(capped) -> capped at 30s below
+    let secs = BASE_BACKOFF.as_secs().saturating_mul(1 << shift);
+    Duration::from_secs(secs).min(MAX_BACKOFF)
+  }
+}
+
+/// Reconciles the desired set of proxy configs (everything persisted in
+/// `proxy_storage`) against their actual process state on an interval,
+/// re-spawning any worker whose PID has died. Crash-looping workers get
+/// exponential backoff and are eventually quarantined (stop being
+/// auto-restarted) once `MAX_RESTARTS` is exceeded.
+pub async fn spawn_proxy_supervisor() {
+  let mut restart_states: HashMap<String, RestartState> = HashMap::new();
+
+  loop {
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    let desired = list_proxy_configs();
+    let desired_ids: std::collections::HashSet<String> =
+      desired.iter().map(|c| c.id.clone()).collect();
+    restart_states.retain(|id, _| desired_ids.contains(id));
+
+    for config in desired {
+      let alive = config.pid.map(is_process_running).unwrap_or(false);
+      if alive {
+        // Healthy again; forget prior crash-loop history.
+        restart_states.remove(&config.id);
+        continue;
+      }
+
+      let state = restart_states
+        .entry(config.id.clone())
+        .or_insert_with(RestartState::fresh);
+
+      if state.quarantined {
+        continue;
+      }
+
+      if Instant::now() < state.next_attempt_at {
+        continue;
+      }
+
+      if state.restarts >= MAX_RESTARTS {
+        log::error!(
+          "Proxy {} has crashed {} times; quarantining (no further auto-restarts)",
+          config.id,
+          state.restarts
+        );
+        state.quarantined = true;
+        continue;
+      }
+
+      log::warn!(
+        "Proxy {} (pid {:?}) is dead, respawning (attempt {})",
+        config.id,
+        config.pid,
+        state.restarts + 1
+      );
+
+      match start_proxy_process_with_id(
+        config.id.clone(),
+        Some(config.upstream.clone()),
+        config.local_port,
+        config.profile_id.clone(),
+        ProcessLifetime::default(),
+      )
+      .await
+      {
+        Ok(_respawned) => {
+          state.restarts += 1;
+          state.next_attempt_at = Instant::now() + state.backoff();
+        }
+        Err(e) => {
+          log::error!("Failed to respawn proxy {}: {}", config.id, e);
+          state.restarts += 1;
+          state.next_attempt_at = Instant::now() + state.backoff();
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,134 @@
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+
+/// Builder for assembling and launching an external browser process bound to
+/// a profile data directory. Mirrors the chained-setter shape of
+/// `std::process::Command` so callers can configure a launch without
+/// immediately spawning it.
+pub trait Runner {
+  fn arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Self;
+  fn args<I, S>(&mut self, args: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>;
+  fn env(&mut self, key: impl AsRef<OsStr>, val: impl AsRef<OsStr>) -> &mut Self;
+  fn envs<I, K, V>(&mut self, vars: I) -> &mut Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<OsStr>,
+    V: AsRef<OsStr>;
+  fn stdout(&mut self, cfg: Stdio) -> &mut Self;
+  fn stderr(&mut self, cfg: Stdio) -> &mut Self;
+  fn start(&mut self) -> io::Result<RunnerProcess>;
+}
+
+/// A `Runner` that launches a browser binary pointed at a profile's data dir.
+pub struct BrowserRunner {
+  command: Command,
+}
+
+impl BrowserRunner {
+  pub fn new(binary_path: impl AsRef<Path>, profile_data_dir: impl AsRef<Path>) -> Self {
+    let mut command = Command::new(binary_path.as_ref());
+    command.arg("--profile").arg(profile_data_dir.as_ref());
+    Self { command }
+  }
+}
+
+impl Runner for BrowserRunner {
+  fn arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Self {
+    self.command.arg(arg);
+    self
+  }
+
+  fn args<I, S>(&mut self, args: I) -> &mut Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+  {
+    self.command.args(args);
+    self
+  }
+
+  fn env(&mut self, key: impl AsRef<OsStr>, val: impl AsRef<OsStr>) -> &mut Self {
+    self.command.env(key, val);
+    self
+  }
+
+  fn envs<I, K, V>(&mut self, vars: I) -> &mut Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<OsStr>,
+    V: AsRef<OsStr>,
+  {
+    self.command.envs(vars);
+    self
+  }
+
+  fn stdout(&mut self, cfg: Stdio) -> &mut Self {
+    self.command.stdout(cfg);
+    self
+  }
+
+  fn stderr(&mut self, cfg: Stdio) -> &mut Self {
+    self.command.stderr(cfg);
+    self
+  }
+
+  fn start(&mut self) -> io::Result<RunnerProcess> {
+    let child = self.command.spawn()?;
+    Ok(RunnerProcess {
+      child,
+      exited: None,
+    })
+  }
+}
+
+/// A handle to a spawned runner process. `try_status` is advisory and
+/// non-blocking: it reaps the child as soon as it exits (via `try_wait`, so
+/// no zombie is left behind on Unix) and keeps returning the same exit
+/// status on subsequent calls.
+pub struct RunnerProcess {
+  child: Child,
+  exited: Option<ExitStatus>,
+}
+
+impl RunnerProcess {
+  pub fn id(&self) -> u32 {
+    self.child.id()
+  }
+
+  /// Non-blocking check of whether the child has exited. Returns
+  /// `Ok(Some(status))` repeatedly once it has, `Ok(None)` while still
+  /// running.
+  pub fn try_status(&mut self) -> io::Result<Option<ExitStatus>> {
+    if let Some(status) = self.exited {
+      return Ok(Some(status));
+    }
+    match self.child.try_wait()? {
+      Some(status) => {
+        self.exited = Some(status);
+        Ok(Some(status))
+      }
+      None => Ok(None),
+    }
+  }
+
+  pub fn wait(&mut self) -> io::Result<ExitStatus> {
+    if let Some(status) = self.exited {
+      return Ok(status);
+    }
+    let status = self.child.wait()?;
+    self.exited = Some(status);
+    Ok(status)
+  }
+
+  pub fn kill(&mut self) -> io::Result<()> {
+    if self.exited.is_some() {
+      return Ok(());
+    }
+    self.child.kill()
+  }
+}
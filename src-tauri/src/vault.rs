@@ -0,0 +1,173 @@
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Prefix marking a field value as a sealed blob produced by this module,
+/// so `open()` can tell a freshly-encrypted value apart from an
+/// already-on-disk legacy plaintext value and let old profiles round-trip
+/// without a migration step.
+const SEALED_PREFIX: &str = "enc:v1:";
+const KEYRING_SERVICE: &str = "foxia-mkt";
+const KEYRING_ACCOUNT: &str = "vault-key";
+
+#[derive(Debug)]
+pub struct VaultKeyError(String);
+
+impl fmt::Display for VaultKeyError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "vault key error: {}", self.0)
+  }
+}
+impl std::error::Error for VaultKeyError {}
+
+/// Look up (or lazily create) the 32-byte AES-256-GCM key in the OS
+/// keyring. Falls back to deriving a key via Argon2id from
+/// `FOXIA_VAULT_PASSPHRASE` when no keyring is available (e.g. headless
+/// CI), so the CLI companion can still operate on encrypted profiles.
+/// With that unset there is no source-visible passphrase to fall back
+/// to, so this fails loudly instead of deriving a publicly-known key
+/// every install would share, which would defeat encryption-at-rest for
+/// every `SealedSecretString` on that box.
+fn vault_key() -> Result<[u8; 32], VaultKeyError> {
+  if let Ok(entry) = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT) {
+    if let Ok(existing) = entry.get_password() {
+      if let Ok(bytes) = STANDARD.decode(existing) {
+        if bytes.len() == 32 {
+          let mut key = [0u8; 32];
+          key.copy_from_slice(&bytes);
+          return Ok(key);
+        }
+      }
+    }
+
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    let _ = entry.set_password(&STANDARD.encode(key));
+    return Ok(key);
+  }
+
+  let passphrase = std::env::var("FOXIA_VAULT_PASSPHRASE").map_err(|_| {
+    VaultKeyError(
+      "no OS keyring available and FOXIA_VAULT_PASSPHRASE is unset; refusing to derive a vault key from a hardcoded passphrase".to_string(),
+    )
+  })?;
+  Ok(derive_key_from_passphrase(&passphrase))
+}
+
+fn derive_key_from_passphrase(passphrase: &str) -> [u8; 32] {
+  // Fixed, app-specific salt: the key is also gated by the OS keyring in
+  // the common case, so this path only matters for environments without
+  // one (CLI/CI), where per-install randomness doesn't buy much anyway.
+  const SALT: &[u8] = b"foxia-mkt-vault-salt-v1";
+  let mut key = [0u8; 32];
+  Argon2::default()
+    .hash_password_into(passphrase.as_bytes(), SALT, &mut key)
+    .expect("Argon2id key derivation must not fail for a fixed-size output");
+  key
+}
+
+/// Encrypt `secret` with AES-256-GCM under `key`, returning a
+/// `SEALED_PREFIX`-tagged base64 blob of `nonce || ciphertext`.
+pub fn seal(secret: &str, key: &[u8; 32]) -> String {
+  let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+  let mut nonce_bytes = [0u8; 12];
+  OsRng.fill_bytes(&mut nonce_bytes);
+  let nonce = Nonce::from_slice(&nonce_bytes);
+
+  let ciphertext = cipher
+    .encrypt(nonce, secret.as_bytes())
+    .expect("AES-256-GCM encryption must not fail for well-formed input");
+
+  let mut payload = Vec::with_capacity(12 + ciphertext.len());
+  payload.extend_from_slice(&nonce_bytes);
+  payload.extend_from_slice(&ciphertext);
+
+  format!("{}{}", SEALED_PREFIX, STANDARD.encode(payload))
+}
+
+#[derive(Debug)]
+pub struct UnsealError(String);
+
+impl fmt::Display for UnsealError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "failed to open sealed secret: {}", self.0)
+  }
+}
+impl std::error::Error for UnsealError {}
+
+/// Decrypt a blob previously produced by `seal`. Returns an error for
+/// anything not carrying `SEALED_PREFIX` so callers can fall back to
+/// treating the value as legacy plaintext.
+pub fn open(blob: &str, key: &[u8; 32]) -> Result<SecretString, UnsealError> {
+  let encoded = blob
+    .strip_prefix(SEALED_PREFIX)
+    .ok_or_else(|| UnsealError("missing sealed-value prefix".to_string()))?;
+
+  let payload = STANDARD
+    .decode(encoded)
+    .map_err(|e| UnsealError(e.to_string()))?;
+  if payload.len() < 12 {
+    return Err(UnsealError("payload too short to contain a nonce".to_string()));
+  }
+  let (nonce_bytes, ciphertext) = payload.split_at(12);
+
+  let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+  let plaintext = cipher
+    .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+    .map_err(|e| UnsealError(e.to_string()))?;
+
+  String::from_utf8(plaintext)
+    .map(SecretString::new)
+    .map_err(|e| UnsealError(e.to_string()))
+}
+
+/// A `String` field that is transparently sealed on serialize and opened
+/// on deserialize, so `BrowserProfile` (and anything else storing
+/// credentials) never holds or logs plaintext outside of active use.
+/// Deserializing a legacy plaintext value (written before this type
+/// existed) is accepted as-is so existing profile JSON keeps loading.
+#[derive(Clone)]
+pub struct SealedSecretString(SecretString);
+
+impl SealedSecretString {
+  pub fn new(plain: impl Into<String>) -> Self {
+    Self(SecretString::new(plain.into()))
+  }
+
+  pub fn expose(&self) -> &str {
+    self.0.expose_secret()
+  }
+}
+
+impl fmt::Debug for SealedSecretString {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "SealedSecretString([redacted])")
+  }
+}
+
+impl Serialize for SealedSecretString {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let key = vault_key().map_err(serde::ser::Error::custom)?;
+    let sealed = seal(self.0.expose_secret(), &key);
+    serializer.serialize_str(&sealed)
+  }
+}
+
+impl<'de> Deserialize<'de> for SealedSecretString {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    let key = vault_key().map_err(serde::de::Error::custom)?;
+    let plain = match open(&raw, &key) {
+      Ok(secret) => secret.expose_secret().to_string(),
+      Err(_) => raw,
+    };
+    Ok(Self(SecretString::new(plain)))
+  }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Errors surfaced by a WebDriver HTTP endpoint, mapped from the
+/// `{ "value": { "error", "message", "stacktrace" } }` envelope the W3C
+/// protocol uses for non-2xx responses.
+#[derive(Debug)]
+pub enum WebDriverError {
+  Transport(String),
+  Decode(String),
+  Protocol {
+    error: String,
+    message: String,
+    stacktrace: String,
+  },
+}
+
+impl std::fmt::Display for WebDriverError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      WebDriverError::Transport(e) => write!(f, "WebDriver transport error: {}", e),
+      WebDriverError::Decode(e) => write!(f, "WebDriver decode error: {}", e),
+      WebDriverError::Protocol { error, message, .. } => {
+        write!(f, "WebDriver error [{}]: {}", error, message)
+      }
+    }
+  }
+}
+impl std::error::Error for WebDriverError {}
+
+#[derive(Debug, Deserialize)]
+struct ErrorEnvelope {
+  error: String,
+  message: String,
+  #[serde(default)]
+  stacktrace: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseEnvelope<T> {
+  value: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Locator {
+  pub using: String, // "css selector" | "xpath" | ...
+  pub value: String,
+}
+
+impl Locator {
+  pub fn css(selector: impl Into<String>) -> Self {
+    Self {
+      using: "css selector".to_string(),
+      value: selector.into(),
+    }
+  }
+
+  pub fn xpath(expr: impl Into<String>) -> Self {
+    Self {
+      using: "xpath".to_string(),
+      value: expr.into(),
+    }
+  }
+}
+
+/// A thin client speaking the W3C WebDriver HTTP protocol to the
+/// geckodriver/Marionette port of a launched profile.
+pub struct WebDriverClient {
+  http: reqwest::Client,
+  base_url: String,
+  session_id: Option<String>,
+}
+
+impl WebDriverClient {
+  /// `base_url` is the geckodriver endpoint, e.g. `http://127.0.0.1:4444`.
+  pub fn new(base_url: impl Into<String>) -> Self {
+    Self {
+      http: reqwest::Client::new(),
+      base_url: base_url.into(),
+      session_id: None,
+    }
+  }
+
+  pub fn session_id(&self) -> Option<&str> {
+    self.session_id.as_deref()
+  }
+
+  async fn request<T: for<'de> Deserialize<'de>>(
+    &self,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<Value>,
+  ) -> Result<T, WebDriverError> {
+    let url = format!("{}{}", self.base_url, path);
+    let mut req = self.http.request(method, &url);
+    if let Some(body) = body {
+      req = req.json(&body);
+    }
+
+    let response = req
+      .send()
+      .await
+      .map_err(|e| WebDriverError::Transport(e.to_string()))?;
+    let status = response.status();
+    let text = response
+      .text()
+      .await
+      .map_err(|e| WebDriverError::Transport(e.to_string()))?;
+
+    if !status.is_success() {
+      let envelope: ResponseEnvelope<ErrorEnvelope> = serde_json::from_str(&text)
+        .map_err(|e| WebDriverError::Decode(format!("{}. Body: {}", e, text)))?;
+      return Err(WebDriverError::Protocol {
+        error: envelope.value.error,
+        message: envelope.value.message,
+        stacktrace: envelope.value.stacktrace,
+      });
+    }
+
+    let envelope: ResponseEnvelope<T> = serde_json::from_str(&text)
+      .map_err(|e| WebDriverError::Decode(format!("{}. Body: {}", e, text)))?;
+    Ok(envelope.value)
+  }
+
+  /// `POST /session` with the given capabilities JSON.
+  pub async fn new_session(&mut self, capabilities: Value) -> Result<String, WebDriverError> {
+    #[derive(Deserialize)]
+    struct NewSessionValue {
+      #[serde(rename = "sessionId")]
+      session_id: String,
+    }
+
+    let body = json!({ "capabilities": { "alwaysMatch": capabilities } });
+    let value: NewSessionValue = self
+      .request(reqwest::Method::POST, "/session", Some(body))
+      .await?;
+    self.session_id = Some(value.session_id.clone());
+    Ok(value.session_id)
+  }
+
+  fn session_path(&self, suffix: &str) -> Result<String, WebDriverError> {
+    let session_id = self
+      .session_id
+      .as_ref()
+      .ok_or_else(|| WebDriverError::Transport("no active session".to_string()))?;
+    Ok(format!("/session/{}{}", session_id, suffix))
+  }
+
+  /// `POST /session/{id}/url`
+  pub async fn navigate_to(&self, url: &str) -> Result<(), WebDriverError> {
+    let path = self.session_path("/url")?;
+    let _: Value = self
+      .request(reqwest::Method::POST, &path, Some(json!({ "url": url })))
+      .await?;
+    Ok(())
+  }
+
+  /// `POST /session/{id}/element`
+  pub async fn find_element(&self, locator: &Locator) -> Result<String, WebDriverError> {
+    #[derive(Deserialize)]
+    struct ElementValue {
+      #[serde(rename = "element-6066-11e4-a52e-4f735466cecf")]
+      element_id: String,
+    }
+
+    let path = self.session_path("/element")?;
+    let value: ElementValue = self
+      .request(
+        reqwest::Method::POST,
+        &path,
+        Some(json!({ "using": locator.using, "value": locator.value })),
+      )
+      .await?;
+    Ok(value.element_id)
+  }
+
+  /// `POST /session/{id}/execute/sync`
+  pub async fn execute_script(&self, script: &str, args: Vec<Value>) -> Result<Value, WebDriverError> {
+    let path = self.session_path("/execute/sync")?;
+    self
+      .request(
+        reqwest::Method::POST,
+        &path,
+        Some(json!({ "script": script, "args": args })),
+      )
+      .await
+  }
+
+  /// `GET /session/{id}/cookie`
+  pub async fn get_cookies(&self) -> Result<Value, WebDriverError> {
+    let path = self.session_path("/cookie")?;
+    self.request(reqwest::Method::GET, &path, None).await
+  }
+
+  /// `DELETE /session/{id}`
+  pub async fn delete_session(&mut self) -> Result<(), WebDriverError> {
+    let path = self.session_path("")?;
+    let _: Value = self.request(reqwest::Method::DELETE, &path, None).await?;
+    self.session_id = None;
+    Ok(())
+  }
+}